@@ -0,0 +1,97 @@
+use chrono::{Timelike, Utc};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::config::Config;
+
+/// How each cycle's capital is spread across ranked opportunities
+#[derive(Debug, Clone, Deserialize)]
+pub struct AllocationConfig {
+    /// Top-ranked opportunities funded per cycle
+    #[serde(default = "default_max_trades_per_cycle")]
+    pub max_trades_per_cycle: usize,
+
+    /// Release the daily budget evenly over the day instead of all at once
+    #[serde(default = "default_pace_daily_budget")]
+    pub pace_daily_budget: bool,
+
+    /// Hours of budget released ahead of the clock when pacing
+    #[serde(default = "default_pace_ahead_hours")]
+    pub pace_ahead_hours: f64,
+}
+
+fn default_max_trades_per_cycle() -> usize { 5 }
+fn default_pace_daily_budget() -> bool { true }
+fn default_pace_ahead_hours() -> f64 { 1.0 }
+
+impl Default for AllocationConfig {
+    fn default() -> Self {
+        Self {
+            max_trades_per_cycle: default_max_trades_per_cycle(),
+            pace_daily_budget: default_pace_daily_budget(),
+            pace_ahead_hours: default_pace_ahead_hours(),
+        }
+    }
+}
+
+/// Capital this cycle may spend: what remains under the daily and exposure limits,
+/// further limited to the share of the daily budget released so far when pacing
+pub fn cycle_budget(config: &Config, daily_spent: f64, remaining: f64) -> f64 {
+    let allocation = &config.allocation;
+    if !allocation.pace_daily_budget {
+        return remaining.max(0.0);
+    }
+
+    let now = Utc::now();
+    let hours_elapsed = now.hour() as f64 + now.minute() as f64 / 60.0;
+    let released = ((hours_elapsed + allocation.pace_ahead_hours) / 24.0).min(1.0);
+    let paced = config.max_daily_spend_usd * released - daily_spent;
+    paced.min(remaining).max(0.0)
+}
+
+/// Walks ranked candidates funding each with its requested stake, scaled down proportionally
+/// when the top `max_trades_per_cycle` together exceed the budget. Only placed trades use up
+/// a slot and budget, so a candidate rejected downstream passes both to the next in line.
+pub struct CycleAllocator {
+    scale: f64,
+    remaining: f64,
+    slots: usize,
+    min_stake: f64,
+}
+
+impl CycleAllocator {
+    pub fn new(stakes: &[f64], budget: f64, config: &Config) -> Self {
+        let requested: f64 = stakes.iter()
+            .filter(|stake| **stake > 0.0)
+            .take(config.allocation.max_trades_per_cycle)
+            .sum();
+        let scale = if requested > 0.0 { (budget / requested).min(1.0) } else { 1.0 };
+        if scale < 1.0 {
+            info!("💰 Cycle budget ${:.2} covers {:.0}% of ${:.2} requested", budget, scale * 100.0, requested);
+        }
+
+        Self {
+            scale,
+            remaining: budget.max(0.0),
+            slots: config.allocation.max_trades_per_cycle,
+            min_stake: config.sizing.min_stake_usd,
+        }
+    }
+
+    /// Amount to fund a candidate requesting `stake`, or None when it falls below `min_stake_usd`
+    pub fn amount_for(&self, stake: f64) -> Option<f64> {
+        let amount = (stake * self.scale).min(self.remaining);
+        (!self.is_done() && stake > 0.0 && amount >= self.min_stake).then_some(amount)
+    }
+
+    /// Record a placed trade that committed `spent`
+    pub fn commit(&mut self, spent: f64) {
+        self.remaining = (self.remaining - spent).max(0.0);
+        self.slots = self.slots.saturating_sub(1);
+    }
+
+    /// Every slot is used or the budget can't fund another minimum stake
+    pub fn is_done(&self) -> bool {
+        self.slots == 0 || self.remaining < self.min_stake
+    }
+}
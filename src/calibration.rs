@@ -0,0 +1,152 @@
+use anyhow::{Result, bail};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::clob::OrderSide;
+use crate::config::Config;
+use crate::executor::Trade;
+use crate::gamma::{GammaClient, GammaMarket, MarketQuery};
+use crate::journal::{EntryContext, TradeJournal};
+
+/// Maximum condition IDs per Gamma request
+const RESOLVE_BATCH_SIZE: usize = 50;
+/// Entry price bucket upper bounds in cents; anything above the last is one bucket
+const PRICE_BUCKETS_CENTS: &[f64] = &[2.0, 5.0, 10.0, 20.0];
+
+/// A filled journal trade whose market has resolved
+struct ResolvedTrade {
+    trade: Trade,
+    context: Option<EntryContext>,
+    won: bool,
+}
+
+/// Realized outcomes of one group of trades
+#[derive(Default)]
+struct Bucket {
+    trades: usize,
+    wins: usize,
+    price_sum: f64,
+    cost: f64,
+    payout: f64,
+}
+
+impl Bucket {
+    fn add(&mut self, resolved: &ResolvedTrade) {
+        let shares = resolved.trade.filled_size;
+        let price = resolved.trade.avg_fill_price();
+        self.trades += 1;
+        self.price_sum += price;
+        self.cost += shares * price;
+        if resolved.won {
+            self.wins += 1;
+            self.payout += shares;
+        }
+    }
+
+    fn implied(&self) -> f64 {
+        self.price_sum / self.trades as f64
+    }
+
+    fn realized(&self) -> f64 {
+        self.wins as f64 / self.trades as f64
+    }
+
+    fn roi(&self) -> f64 {
+        if self.cost > 0.0 { self.payout / self.cost - 1.0 } else { 0.0 }
+    }
+}
+
+fn price_bucket(price: f64) -> String {
+    let cents = price * 100.0;
+    let mut lower = 0.0;
+    for &upper in PRICE_BUCKETS_CENTS {
+        if cents <= upper {
+            return format!("{:>2}-{}¢", lower, upper);
+        }
+        lower = upper;
+    }
+    format!(">{}¢", lower)
+}
+
+/// Build the calibration table from the trade journal and resolved Gamma markets,
+/// print it and write it to `csv_path` (the `calibration-report` subcommand)
+pub async fn report(config: &Config, csv_path: &str) -> Result<()> {
+    let Some(journal_path) = config.trade_journal_path.as_deref().filter(|p| !p.is_empty()) else {
+        bail!("trade_journal_path is not set in config.json");
+    };
+
+    // Latest state of each trade, with the context recorded when it was placed
+    let mut trades: HashMap<String, (Trade, Option<EntryContext>)> = HashMap::new();
+    for entry in TradeJournal::load(Path::new(journal_path))? {
+        let slot = trades.entry(entry.trade.id.clone()).or_insert_with(|| (entry.trade.clone(), None));
+        slot.0 = entry.trade;
+        if entry.context.is_some() {
+            slot.1 = entry.context;
+        }
+    }
+
+    // Hedged basket legs pay out regardless of outcome and would skew calibration
+    let filled: Vec<(Trade, Option<EntryContext>)> = trades.into_values()
+        .filter(|(trade, _)| trade.order_side == OrderSide::Buy && trade.filled_size > 0.0 && trade.basket_id.is_none())
+        .collect();
+    info!("📒 {} filled trades in journal", filled.len());
+
+    let markets = fetch_resolved_markets(&filled).await;
+    let mut resolved = Vec::new();
+    for (trade, context) in filled {
+        let Some(market) = markets.get(&trade.condition_id) else { continue };
+        let Some(winner) = market.resolved_outcome() else { continue };
+        let index = market.token_ids().iter().position(|t| *t == trade.token_id).unwrap_or(trade.outcome_index);
+        resolved.push(ResolvedTrade { trade, context, won: index == winner });
+    }
+
+    if resolved.is_empty() {
+        info!("No journal trades have resolved yet");
+        return Ok(());
+    }
+
+    let mut groups: BTreeMap<(&str, String), Bucket> = BTreeMap::new();
+    for r in &resolved {
+        let category = r.context.as_ref().map(|c| c.category.to_lowercase()).filter(|c| !c.is_empty());
+        let discovery = r.context.as_ref().map(|c| c.discovery_type.clone());
+        groups.entry(("all", "all".to_string())).or_default().add(r);
+        groups.entry(("price", price_bucket(r.trade.avg_fill_price()))).or_default().add(r);
+        groups.entry(("category", category.unwrap_or_else(|| "unknown".to_string()))).or_default().add(r);
+        groups.entry(("discovery", discovery.unwrap_or_else(|| "unknown".to_string()))).or_default().add(r);
+    }
+
+    println!("{:<10} {:<18} {:>6} {:>9} {:>9} {:>8} {:>10} {:>8}",
+        "group", "bucket", "trades", "implied", "realized", "gap", "cost", "roi");
+    let mut csv = String::from("group,bucket,trades,wins,implied,realized,gap,cost_usd,payout_usd,roi\n");
+    for ((group, bucket), stats) in &groups {
+        println!("{:<10} {:<18} {:>6} {:>8.1}% {:>8.1}% {:>+7.1}% {:>10.2} {:>+7.0}%",
+            group, bucket, stats.trades, stats.implied() * 100.0, stats.realized() * 100.0,
+            (stats.realized() - stats.implied()) * 100.0, stats.cost, stats.roi() * 100.0);
+        csv.push_str(&format!("{},{},{},{},{:.4},{:.4},{:.4},{:.2},{:.2},{:.4}\n",
+            group, bucket.replace(',', " "), stats.trades, stats.wins, stats.implied(), stats.realized(),
+            stats.realized() - stats.implied(), stats.cost, stats.payout, stats.roi()));
+    }
+
+    std::fs::write(csv_path, csv)?;
+    info!("💾 Wrote calibration report for {} resolved trades to {}", resolved.len(), csv_path);
+    Ok(())
+}
+
+/// Fetch closed markets for the trades' condition IDs
+async fn fetch_resolved_markets(trades: &[(Trade, Option<EntryContext>)]) -> HashMap<String, GammaMarket> {
+    let mut condition_ids: Vec<&str> = trades.iter().map(|(t, _)| t.condition_id.as_str()).collect();
+    condition_ids.sort_unstable();
+    condition_ids.dedup();
+
+    let gamma = GammaClient::new();
+    let mut markets = HashMap::new();
+    for chunk in condition_ids.chunks(RESOLVE_BATCH_SIZE) {
+        let query = MarketQuery::new().condition_ids(chunk).closed(true).limit(chunk.len() as u32);
+        match gamma.fetch_markets(&query).await {
+            Ok(batch) => markets.extend(batch.into_iter().map(|m| (m.condition_id.clone(), m))),
+            Err(e) => warn!("Failed to fetch resolved markets: {}", e),
+        }
+    }
+    markets
+}
@@ -0,0 +1,643 @@
+use anyhow::{Context, Result, bail};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, signature::hazmat::PrehashSigner};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::clob::{keccak256, pubkey_to_address};
+use crate::positions::Position;
+
+/// Conditional Tokens Framework contract holding outcome tokens
+const CTF: &str = "4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+/// USDC.e, the collateral of standard markets
+const USDC_E: &str = "2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+/// Adapter that redeems positions in neg-risk markets
+const NEG_RISK_ADAPTER: &str = "d91E80cF2E7be2e162c6513ceD06f1dD0dA35296";
+/// Gas limit headroom over the node's estimate, in percent
+const GAS_HEADROOM_PCT: u128 = 20;
+/// Priority fee when the node doesn't suggest one (30 gwei, the Polygon minimum)
+const DEFAULT_PRIORITY_FEE: u128 = 30_000_000_000;
+/// Submissions before a redemption is given up
+const MAX_REDEEM_ATTEMPTS: u32 = 3;
+/// Seconds a transaction may stay unmined before it is replaced with higher fees
+const TX_TIMEOUT_SECS: u64 = 180;
+/// Fee increase of a replacement transaction in percent (nodes require at least 10)
+const FEE_BUMP_PCT: u128 = 25;
+
+/// A submitted transaction, kept so it can be re-signed at the same nonce with higher fees
+#[derive(Debug, Clone)]
+pub struct SentTx {
+    to: [u8; 20],
+    data: Vec<u8>,
+    nonce: u128,
+    gas_limit: u128,
+    priority_fee: u128,
+    max_fee: u128,
+    /// Hash of every version sent at this nonce, latest last; any of them may be mined
+    hashes: Vec<String>,
+    sent_at: Instant,
+}
+
+impl SentTx {
+    fn hash(&self) -> &str {
+        self.hashes.last().map_or("", |h| h.as_str())
+    }
+
+    fn timed_out(&self) -> bool {
+        self.sent_at.elapsed().as_secs() >= TX_TIMEOUT_SECS
+    }
+}
+
+/// Minimal Ethereum JSON-RPC client that signs EIP-1559 transactions with the trading key
+pub struct ChainClient {
+    http: Client,
+    rpc_url: String,
+    signing_key: SigningKey,
+    address: [u8; 20],
+    chain_id: u64,
+}
+
+impl ChainClient {
+    /// Connect to `rpc_url`, reading the chain ID from the node (Polygon, or a local fork)
+    pub async fn connect(rpc_url: &str, private_key: &str) -> Result<Self> {
+        let key_hex = private_key.strip_prefix("0x").unwrap_or(private_key);
+        let key_bytes = hex::decode(key_hex).context("Invalid private key hex")?;
+        let signing_key = SigningKey::from_slice(&key_bytes).context("Invalid private key")?;
+        let address = pubkey_to_address(&signing_key);
+
+        let mut client = Self {
+            http: Client::builder().timeout(std::time::Duration::from_secs(30)).build()?,
+            rpc_url: rpc_url.to_string(),
+            signing_key,
+            address,
+            chain_id: 0,
+        };
+        client.chain_id = parse_quantity(&client.rpc("eth_chainId", json!([])).await?)? as u64;
+        Ok(client)
+    }
+
+    pub fn address(&self) -> String { format!("0x{}", hex::encode(self.address)) }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let resp: Value = self.http.post(&self.rpc_url).json(&body).send().await
+            .with_context(|| format!("{} request failed", method))?
+            .json().await
+            .with_context(|| format!("Failed to parse {} response", method))?;
+
+        if let Some(error) = resp.get("error") {
+            bail!("{} failed: {}", method, error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error"));
+        }
+        Ok(resp.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Read-only contract call
+    async fn call(&self, to: &[u8; 20], data: &[u8]) -> Result<Vec<u8>> {
+        let params = json!([{ "to": hex_prefixed(to), "data": hex_prefixed(data) }, "latest"]);
+        let result = self.rpc("eth_call", params).await?;
+        let result = result.as_str().context("eth_call returned no data")?;
+        Ok(hex::decode(result.trim_start_matches("0x"))?)
+    }
+
+    /// Sign and submit a contract call.
+    /// Gas is estimated by the node; fees follow the latest base fee plus the suggested tip.
+    async fn send_transaction(&self, to: &[u8; 20], data: &[u8]) -> Result<SentTx> {
+        let from = self.address();
+        let nonce = parse_quantity(&self.rpc("eth_getTransactionCount", json!([from, "pending"])).await?)?;
+        let estimate = parse_quantity(&self.rpc("eth_estimateGas",
+            json!([{ "from": from, "to": hex_prefixed(to), "data": hex_prefixed(data) }])).await?)?;
+        let (priority_fee, max_fee) = self.fees().await?;
+
+        let mut tx = SentTx {
+            to: *to,
+            data: data.to_vec(),
+            nonce,
+            gas_limit: estimate * (100 + GAS_HEADROOM_PCT) / 100,
+            priority_fee,
+            max_fee,
+            hashes: Vec::new(),
+            sent_at: Instant::now(),
+        };
+        self.broadcast(&mut tx).await?;
+        Ok(tx)
+    }
+
+    /// Re-sign a stuck transaction at the same nonce with fees raised by `FEE_BUMP_PCT`
+    /// (or to the current market rate, if higher) and broadcast it again
+    async fn replace_transaction(&self, tx: &mut SentTx) -> Result<()> {
+        let (priority_fee, max_fee) = self.fees().await?;
+        tx.priority_fee = bump_fee(tx.priority_fee).max(priority_fee);
+        tx.max_fee = bump_fee(tx.max_fee).max(max_fee).max(tx.priority_fee);
+        self.broadcast(tx).await
+    }
+
+    /// Suggested (priority fee, max fee) per gas
+    async fn fees(&self) -> Result<(u128, u128)> {
+        let priority_fee = match self.rpc("eth_maxPriorityFeePerGas", json!([])).await {
+            Ok(fee) => parse_quantity(&fee)?.max(DEFAULT_PRIORITY_FEE),
+            Err(e) => {
+                debug!("No priority fee suggestion ({}), using default", e);
+                DEFAULT_PRIORITY_FEE
+            }
+        };
+        let block = self.rpc("eth_getBlockByNumber", json!(["latest", false])).await?;
+        let base_fee = parse_quantity(block.get("baseFeePerGas").context("Latest block has no base fee")?)?;
+        Ok((priority_fee, base_fee * 2 + priority_fee))
+    }
+
+    /// Sign the transaction as EIP-1559 and submit it, recording the new hash
+    async fn broadcast(&self, tx: &mut SentTx) -> Result<()> {
+        let mut fields = tx_fields(self.chain_id, tx);
+        let mut unsigned = vec![0x02];
+        unsigned.extend(rlp_list(&fields));
+
+        let (sig, recid): (Signature, RecoveryId) = self.signing_key.sign_prehash(&keccak256(&unsigned))
+            .map_err(|e| anyhow::anyhow!("Signing failed: {}", e))?;
+        let (r, s) = sig.split_bytes();
+        fields.push(rlp_uint(recid.is_y_odd() as u128));
+        fields.push(rlp_bytes(trim_leading_zeros(&r)));
+        fields.push(rlp_bytes(trim_leading_zeros(&s)));
+        let mut raw = vec![0x02];
+        raw.extend(rlp_list(&fields));
+
+        let hash = self.rpc("eth_sendRawTransaction", json!([hex_prefixed(&raw)])).await?;
+        let hash = hash.as_str().context("eth_sendRawTransaction returned no hash")?.to_string();
+        debug!("Sent tx {} (nonce {}, gas {}, max fee {} wei)", hash, tx.nonce, tx.gas_limit, tx.max_fee);
+        tx.hashes.push(hash);
+        tx.sent_at = Instant::now();
+        Ok(())
+    }
+
+    /// Receipt status of a transaction: `None` while unmined, then whether it succeeded
+    async fn receipt_status(&self, tx_hash: &str) -> Result<Option<bool>> {
+        let receipt = self.rpc("eth_getTransactionReceipt", json!([tx_hash])).await?;
+        if receipt.is_null() {
+            return Ok(None);
+        }
+        let status = receipt.get("status").context("Receipt has no status")?;
+        Ok(Some(parse_quantity(status)? == 1))
+    }
+
+    /// Receipt status of whichever version of a transaction was mined, with its hash
+    async fn sent_status(&self, tx: &SentTx) -> Result<Option<(bool, String)>> {
+        for hash in tx.hashes.iter().rev() {
+            if let Some(success) = self.receipt_status(hash).await? {
+                return Ok(Some((success, hash.clone())));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replace the transaction if it has been waiting longer than `TX_TIMEOUT_SECS`
+    async fn unstick(&self, tx: &mut SentTx, label: &str) {
+        if !tx.timed_out() {
+            return;
+        }
+        match self.replace_transaction(tx).await {
+            Ok(()) => info!("⛓️ {} unmined after {}s - replaced with higher fees: {}", label, TX_TIMEOUT_SECS, tx.hash()),
+            Err(e) => warn!("Failed to replace stuck {} tx {}: {}", label, tx.hash(), e),
+        }
+    }
+
+    /// CTF balance of an outcome token (6 decimals)
+    async fn token_balance(&self, token_id: &str) -> Result<u128> {
+        let mut data = selector("balanceOf(address,uint256)").to_vec();
+        data.extend(word_address(&self.address));
+        data.extend(decimal_word(token_id)?);
+        Ok(word_to_u128(&self.call(&contract(CTF), &data).await?))
+    }
+
+    async fn adapter_approved(&self) -> Result<bool> {
+        let mut data = selector("isApprovedForAll(address,address)").to_vec();
+        data.extend(word_address(&self.address));
+        data.extend(word_address(&contract(NEG_RISK_ADAPTER)));
+        Ok(word_to_u128(&self.call(&contract(CTF), &data).await?) == 1)
+    }
+}
+
+/// A won position waiting to be redeemed on chain
+#[derive(Debug, Clone)]
+struct Redemption {
+    condition_id: String,
+    question: String,
+    token_id: String,
+    outcome_index: usize,
+    neg_risk: bool,
+    tx: Option<SentTx>,
+    attempts: u32,
+}
+
+/// A redemption whose transaction succeeded
+#[derive(Debug, Clone)]
+pub struct Redeemed {
+    pub question: String,
+    pub tx_hash: String,
+}
+
+/// Outcome of trying to submit a redemption
+enum Submission {
+    Sent(SentTx),
+    /// No tokens left to redeem
+    NothingToRedeem,
+    /// The adapter approval hasn't been mined yet
+    AwaitingApproval,
+}
+
+/// Redeems winning outcome tokens for USDC through the CTF contract or the neg-risk adapter,
+/// submitting each transaction in one cycle and checking its receipt in later ones
+pub struct Redeemer {
+    client: ChainClient,
+    pending: Vec<Redemption>,
+    adapter_approved: bool,
+    /// `setApprovalForAll` for the neg-risk adapter, until its receipt is seen
+    approval_tx: Option<SentTx>,
+}
+
+impl Redeemer {
+    pub async fn connect(rpc_url: &str, private_key: &str) -> Result<Self> {
+        let client = ChainClient::connect(rpc_url, private_key).await?;
+        info!("⛓️ Redemption client connected to chain {} for {}", client.chain_id, client.address());
+        Ok(Self { client, pending: Vec::new(), adapter_approved: false, approval_tx: None })
+    }
+
+    /// Queue a settled winning position for redemption
+    pub fn queue(&mut self, position: &Position) {
+        // Standard redemption covers every outcome of a condition; the adapter redeems per token
+        let queued = self.pending.iter().any(|r| {
+            r.condition_id == position.condition_id && (!position.neg_risk || r.token_id == position.token_id)
+        });
+        if queued {
+            return;
+        }
+        self.pending.push(Redemption {
+            condition_id: position.condition_id.clone(),
+            question: position.question.clone(),
+            token_id: position.token_id.clone(),
+            outcome_index: position.outcome_index,
+            neg_risk: position.neg_risk,
+            tx: None,
+            attempts: 0,
+        });
+    }
+
+    /// Check submitted redemptions and submit queued ones, returning those confirmed
+    pub async fn process(&mut self) -> Vec<Redeemed> {
+        let mut redeemed = Vec::new();
+
+        for mut redemption in std::mem::take(&mut self.pending) {
+            if let Some(tx) = redemption.tx.as_mut() {
+                match self.client.sent_status(tx).await {
+                    Ok(Some((true, tx_hash))) => {
+                        info!("💸 Redeemed {} ({})", redemption.question, tx_hash);
+                        redeemed.push(Redeemed { question: redemption.question, tx_hash });
+                        continue;
+                    }
+                    Ok(Some((false, tx_hash))) => {
+                        warn!("⚠️ Redemption reverted for {} ({})", redemption.question, tx_hash);
+                        redemption.tx = None;
+                    }
+                    Ok(None) => self.client.unstick(tx, "Redemption").await,
+                    Err(e) => warn!("Failed to fetch receipt {}: {}", tx.hash(), e),
+                }
+            }
+
+            if redemption.tx.is_none() {
+                if redemption.attempts >= MAX_REDEEM_ATTEMPTS {
+                    warn!("⚠️ Giving up redeeming {} after {} attempts", redemption.question, redemption.attempts);
+                    continue;
+                }
+                match self.submit(&redemption).await {
+                    Ok(Submission::Sent(tx)) => {
+                        info!("⛓️ Redemption submitted for {}: {}", redemption.question, tx.hash());
+                        redemption.attempts += 1;
+                        redemption.tx = Some(tx);
+                    }
+                    Ok(Submission::NothingToRedeem) => {
+                        info!("No {} tokens left to redeem for {}", redemption.token_id, redemption.question);
+                        continue;
+                    }
+                    Ok(Submission::AwaitingApproval) => {
+                        debug!("Redemption of {} waits for the adapter approval", redemption.question);
+                    }
+                    Err(e) => {
+                        warn!("Failed to submit redemption for {}: {}", redemption.question, e);
+                        redemption.attempts += 1;
+                    }
+                }
+            }
+
+            self.pending.push(redemption);
+        }
+
+        redeemed
+    }
+
+    /// Submit the redemption transaction
+    async fn submit(&mut self, redemption: &Redemption) -> Result<Submission> {
+        let condition_id = bytes32(&redemption.condition_id)?;
+        let balance = self.client.token_balance(&redemption.token_id).await?;
+        if balance == 0 {
+            return Ok(Submission::NothingToRedeem);
+        }
+
+        if !redemption.neg_risk {
+            let data = ctf_redeem_calldata(&condition_id);
+            return self.client.send_transaction(&contract(CTF), &data).await.map(Submission::Sent);
+        }
+
+        // The adapter pulls outcome tokens from the CTF, which needs operator approval once
+        if !self.ensure_adapter_approved().await? {
+            return Ok(Submission::AwaitingApproval);
+        }
+
+        let mut amounts = [0u128; 2];
+        amounts[redemption.outcome_index.min(1)] = balance;
+        let data = adapter_redeem_calldata(&condition_id, amounts);
+        self.client.send_transaction(&contract(NEG_RISK_ADAPTER), &data).await.map(Submission::Sent)
+    }
+
+    /// Whether the adapter may move our CTF tokens, sending `setApprovalForAll` if needed.
+    /// The flag is set only once the approval's receipt shows success (or the chain reports it).
+    async fn ensure_adapter_approved(&mut self) -> Result<bool> {
+        if self.adapter_approved {
+            return Ok(true);
+        }
+
+        if let Some(tx) = self.approval_tx.as_mut() {
+            match self.client.sent_status(tx).await? {
+                Some((true, tx_hash)) => {
+                    info!("⛓️ Neg-risk adapter approval confirmed: {}", tx_hash);
+                    self.approval_tx = None;
+                    self.adapter_approved = true;
+                    return Ok(true);
+                }
+                Some((false, tx_hash)) => {
+                    warn!("⚠️ Neg-risk adapter approval reverted ({})", tx_hash);
+                    self.approval_tx = None;
+                }
+                None => {
+                    self.client.unstick(tx, "Adapter approval").await;
+                    return Ok(false);
+                }
+            }
+        }
+
+        if self.client.adapter_approved().await? {
+            self.adapter_approved = true;
+            return Ok(true);
+        }
+
+        let tx = self.client.send_transaction(&contract(CTF), &approval_calldata(&contract(NEG_RISK_ADAPTER))).await?;
+        info!("⛓️ Approving neg-risk adapter on CTF: {}", tx.hash());
+        self.approval_tx = Some(tx);
+        Ok(false)
+    }
+}
+
+// === Calldata ===
+
+/// `setApprovalForAll(operator, true)`
+fn approval_calldata(operator: &[u8; 20]) -> Vec<u8> {
+    let mut data = selector("setApprovalForAll(address,bool)").to_vec();
+    data.extend(word_address(operator));
+    data.extend(word_u128(1));
+    data
+}
+
+/// CTF `redeemPositions(collateral, parentCollectionId, conditionId, indexSets = [1, 2])`
+fn ctf_redeem_calldata(condition_id: &[u8; 32]) -> Vec<u8> {
+    let mut data = selector("redeemPositions(address,bytes32,bytes32,uint256[])").to_vec();
+    data.extend(word_address(&contract(USDC_E)));
+    data.extend([0u8; 32]);
+    data.extend(condition_id);
+    data.extend(word_u128(4 * 32));
+    data.extend(word_u128(2));
+    data.extend(word_u128(1));
+    data.extend(word_u128(2));
+    data
+}
+
+/// Adapter `redeemPositions(conditionId, amounts = [yes, no])`
+fn adapter_redeem_calldata(condition_id: &[u8; 32], amounts: [u128; 2]) -> Vec<u8> {
+    let mut data = selector("redeemPositions(bytes32,uint256[])").to_vec();
+    data.extend(condition_id);
+    data.extend(word_u128(2 * 32));
+    data.extend(word_u128(2));
+    data.extend(word_u128(amounts[0]));
+    data.extend(word_u128(amounts[1]));
+    data
+}
+
+/// RLP fields of an unsigned EIP-1559 transaction with an empty access list
+fn tx_fields(chain_id: u64, tx: &SentTx) -> Vec<Vec<u8>> {
+    vec![
+        rlp_uint(chain_id as u128),
+        rlp_uint(tx.nonce),
+        rlp_uint(tx.priority_fee),
+        rlp_uint(tx.max_fee),
+        rlp_uint(tx.gas_limit),
+        rlp_bytes(&tx.to),
+        rlp_uint(0),
+        rlp_bytes(&tx.data),
+        rlp_list(&[]),
+    ]
+}
+
+fn bump_fee(fee: u128) -> u128 {
+    fee * (100 + FEE_BUMP_PCT) / 100
+}
+
+// === Encoding Helpers ===
+
+fn hex_prefixed(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn parse_quantity(value: &Value) -> Result<u128> {
+    let s = value.as_str().context("Expected a hex quantity")?;
+    Ok(u128::from_str_radix(s.trim_start_matches("0x"), 16)?)
+}
+
+fn contract(address_hex: &str) -> [u8; 20] {
+    let mut address = [0u8; 20];
+    hex::decode_to_slice(address_hex, &mut address).expect("valid contract address");
+    address
+}
+
+fn bytes32(hex_str: &str) -> Result<[u8; 32]> {
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(hex_str.trim_start_matches("0x"), &mut out)
+        .with_context(|| format!("Invalid bytes32 {}", hex_str))?;
+    Ok(out)
+}
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn word_u128(v: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&v.to_be_bytes());
+    word
+}
+
+fn word_address(address: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+/// Big-endian uint256 word from a decimal string (token IDs exceed u128)
+fn decimal_word(decimal: &str) -> Result<[u8; 32]> {
+    let mut word = [0u8; 32];
+    for c in decimal.chars() {
+        let mut carry = c.to_digit(10).with_context(|| format!("Invalid decimal {}", decimal))?;
+        for byte in word.iter_mut().rev() {
+            let v = *byte as u32 * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            bail!("Decimal {} overflows uint256", decimal);
+        }
+    }
+    Ok(word)
+}
+
+fn word_to_u128(data: &[u8]) -> u128 {
+    data.get(16..32).map_or(0, |low| u128::from_be_bytes(low.try_into().unwrap_or_default()))
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn rlp_length_prefix(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let len_bytes = len.to_be_bytes();
+    let len_bytes = trim_leading_zeros(&len_bytes);
+    let mut prefix = vec![offset + 55 + len_bytes.len() as u8];
+    prefix.extend_from_slice(len_bytes);
+    prefix
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_length_prefix(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn rlp_uint(v: u128) -> Vec<u8> {
+    rlp_bytes(trim_leading_zeros(&v.to_be_bytes()))
+}
+
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = rlp_length_prefix(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectors_match_known_signatures() {
+        assert_eq!(hex::encode(selector("redeemPositions(address,bytes32,bytes32,uint256[])")), "01b7037c");
+        assert_eq!(hex::encode(selector("redeemPositions(bytes32,uint256[])")), "dbeccb23");
+        assert_eq!(hex::encode(selector("balanceOf(address,uint256)")), "00fdd58e");
+        assert_eq!(hex::encode(selector("setApprovalForAll(address,bool)")), "a22cb465");
+        assert_eq!(hex::encode(selector("isApprovedForAll(address,address)")), "e985e9c5");
+    }
+
+    #[test]
+    fn rlp_encodes_reference_vectors() {
+        assert_eq!(hex::encode(rlp_bytes(b"dog")), "83646f67");
+        assert_eq!(hex::encode(rlp_bytes(b"")), "80");
+        assert_eq!(hex::encode(rlp_bytes(&[0x0f])), "0f");
+        assert_eq!(hex::encode(rlp_bytes(&[0x80])), "8180");
+        assert_eq!(hex::encode(rlp_list(&[rlp_bytes(b"cat"), rlp_bytes(b"dog")])), "c88363617483646f67");
+        assert_eq!(hex::encode(rlp_list(&[])), "c0");
+        assert_eq!(hex::encode(rlp_uint(0)), "80");
+        assert_eq!(hex::encode(rlp_uint(15)), "0f");
+        assert_eq!(hex::encode(rlp_uint(1024)), "820400");
+
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        let encoded = rlp_bytes(lorem);
+        assert_eq!(hex::encode(&encoded[..2]), "b838");
+        assert_eq!(&encoded[2..], lorem);
+
+        // [ [], [[]], [ [], [[]] ] ]
+        let empty = rlp_list(&[]);
+        let nested = rlp_list(std::slice::from_ref(&empty));
+        let set = rlp_list(&[empty.clone(), nested.clone(), rlp_list(&[empty, nested])]);
+        assert_eq!(hex::encode(set), "c7c0c1c0c3c0c1c0");
+    }
+
+    #[test]
+    fn decimal_word_encodes_uint256() {
+        assert_eq!(decimal_word("0").unwrap(), [0u8; 32]);
+        assert_eq!(decimal_word("1024").unwrap(), word_u128(1024));
+        assert_eq!(decimal_word(&u128::MAX.to_string()).unwrap(), word_u128(u128::MAX));
+
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(decimal_word(max).unwrap(), [0xff; 32]);
+        assert!(decimal_word("115792089237316195423570985008687907853269984665640564039457584007913129639936").is_err());
+        assert!(decimal_word("12a").is_err());
+    }
+
+    #[test]
+    fn ctf_redeem_calldata_layout() {
+        let condition_id = bytes32(&format!("0x{}", "ab".repeat(32))).unwrap();
+        let expected = [
+            "01b7037c",
+            "0000000000000000000000002791bca1f2de4661ed88a30c99a7a9449aa84174",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "abababababababababababababababababababababababababababababababab",
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+        ].concat();
+        assert_eq!(hex::encode(ctf_redeem_calldata(&condition_id)), expected);
+    }
+
+    #[test]
+    fn adapter_redeem_calldata_layout() {
+        let condition_id = bytes32(&"cd".repeat(32)).unwrap();
+        let expected = [
+            "dbeccb23",
+            "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000f4240",
+        ].concat();
+        assert_eq!(hex::encode(adapter_redeem_calldata(&condition_id, [0, 1_000_000])), expected);
+    }
+
+    #[test]
+    fn approval_calldata_layout() {
+        let expected = [
+            "a22cb465",
+            "000000000000000000000000d91e80cf2e7be2e162c6513ced06f1dd0da35296",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        ].concat();
+        assert_eq!(hex::encode(approval_calldata(&contract(NEG_RISK_ADAPTER))), expected);
+    }
+
+    #[test]
+    fn replacement_fees_clear_the_node_minimum_bump() {
+        assert_eq!(bump_fee(DEFAULT_PRIORITY_FEE), 37_500_000_000);
+        assert!(bump_fee(1_000) >= 1_100);
+    }
+}
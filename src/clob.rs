@@ -2,11 +2,11 @@ use anyhow::{Result, Context, bail};
 use hmac::{Hmac, Mac};
 use k256::ecdsa::{SigningKey, signature::hazmat::PrehashSigner, RecoveryId};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tiny_keccak::{Hasher, Keccak};
-use tracing::{info, warn, debug};
+use tracing::{info, warn};
 
 const CLOB_URL: &str = "https://clob.polymarket.com";
 const CHAIN_ID: u64 = 137;
@@ -15,8 +15,8 @@ const NEG_RISK_CTF_EXCHANGE: &str = "4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
 
 type HmacSha256 = Hmac<Sha256>;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum OrderSide { Buy, Sell }
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum OrderSide { #[default] Buy, Sell }
 
 pub struct ClobClient {
     http: Client,
@@ -38,6 +38,14 @@ pub struct OrderResponse {
     pub error_msg: Option<String>,
 }
 
+/// L2 API credentials derived from the wallet signature
+#[derive(Debug, Clone)]
+pub struct ApiCredentials {
+    pub api_key: String,
+    pub secret: String,
+    pub passphrase: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct ApiKeyResponse {
     #[serde(rename = "apiKey")]
@@ -61,9 +69,18 @@ impl ClobClient {
         })
     }
 
-    pub fn is_authenticated(&self) -> bool { self.authenticated }
     pub fn address(&self) -> String { format!("0x{}", hex::encode(self.address)) }
 
+    /// API credentials for authenticated channels (e.g. the user WebSocket)
+    pub fn credentials(&self) -> Option<ApiCredentials> {
+        if !self.authenticated { return None; }
+        Some(ApiCredentials {
+            api_key: self.api_key.clone(),
+            secret: self.api_secret.clone(),
+            passphrase: self.api_passphrase.clone(),
+        })
+    }
+
     /// Derive API credentials via EIP-712 ClobAuth signature
     pub async fn authenticate(&mut self) -> Result<()> {
         info!("🔑 Deriving CLOB API key for {}...", self.address());
@@ -222,7 +239,7 @@ impl ClobClient {
 
 // === Crypto Helpers ===
 
-fn keccak256(data: &[u8]) -> [u8; 32] {
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Keccak::v256();
     let mut out = [0u8; 32];
     hasher.update(data);
@@ -234,7 +251,7 @@ fn current_timestamp() -> String {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs().to_string()
 }
 
-fn pubkey_to_address(key: &SigningKey) -> [u8; 20] {
+pub fn pubkey_to_address(key: &SigningKey) -> [u8; 20] {
     let pubkey = key.verifying_key();
     let pubkey_bytes = pubkey.to_encoded_point(false);
     let hash = keccak256(&pubkey_bytes.as_bytes()[1..]); // skip 0x04 prefix
@@ -1,5 +1,12 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
+use tracing::warn;
+
+use crate::allocator::AllocationConfig;
+use crate::ladder::EntryLadderConfig;
+use crate::scoring::ScoringConfig;
+use crate::sizing::SizingConfig;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -31,10 +38,30 @@ pub struct Config {
     #[serde(default = "default_max_total_exposure")]
     pub max_total_exposure_usd: f64,
 
-    /// Categories to trade (empty = all)
+    /// Categories to trade, matched against Gamma tags/categories (empty = all)
     #[serde(default)]
     pub categories: Vec<String>,
 
+    /// Match question keywords for markets that carry no Gamma tag metadata
+    #[serde(default = "default_category_keyword_fallback")]
+    pub category_keyword_fallback: bool,
+
+    /// Top-scored opportunities per cycle checked against live order books (0 = skip book filters)
+    #[serde(default = "default_book_filter_shortlist")]
+    pub book_filter_shortlist: usize,
+
+    /// Maximum bid/ask spread in cents at entry
+    #[serde(default = "default_max_spread_cents")]
+    pub max_spread_cents: f64,
+
+    /// Ticks above our entry price counted as usable ask depth
+    #[serde(default = "default_depth_ticks")]
+    pub depth_ticks: u32,
+
+    /// Maximum fraction of that ask depth a single order may consume
+    #[serde(default = "default_max_depth_fraction")]
+    pub max_depth_fraction: f64,
+
     /// Scan interval in seconds for new markets
     #[serde(default = "default_scan_interval")]
     pub scan_interval_secs: u64,
@@ -51,6 +78,51 @@ pub struct Config {
     #[serde(default = "default_partial_sell_multiplier")]
     pub partial_sell_multiplier: f64,
 
+    /// Take-profit tiers; when empty, half sells at `partial_sell_multiplier`
+    /// and the rest at `auto_sell_multiplier`
+    #[serde(default)]
+    pub take_profit_ladder: Vec<TakeProfitTier>,
+
+    /// Sell when price falls to this fraction of entry (e.g. 0.5 = -50%)
+    #[serde(default)]
+    pub stop_loss_fraction: Option<f64>,
+
+    /// Sell when price falls this fraction below its high-water mark (once above entry)
+    #[serde(default)]
+    pub trailing_stop_pct: Option<f64>,
+
+    /// Sell positions held longer than this many hours
+    #[serde(default)]
+    pub max_holding_hours: Option<f64>,
+
+    /// Sell positions this many hours before their market's end date
+    #[serde(default)]
+    pub exit_before_end_hours: Option<f64>,
+
+    /// Place sell orders for exit signals (otherwise only notify)
+    #[serde(default = "default_execute_exits")]
+    pub execute_exits: bool,
+
+    /// Live sells still unfilled after this many seconds are cancelled and re-placed at the new price
+    #[serde(default = "default_sell_order_ttl")]
+    pub sell_order_ttl_secs: u64,
+
+    /// Polygon JSON-RPC endpoint used to redeem winning positions
+    #[serde(default = "default_rpc_url")]
+    pub rpc_url: String,
+
+    /// Redeem winning outcome tokens for USDC once their market resolves (live only)
+    #[serde(default = "default_redeem_winnings")]
+    pub redeem_winnings: bool,
+
+    /// Wallet holding the funds; must be the trading key's address, which places orders and redeems
+    #[serde(default)]
+    pub funder_address: Option<String>,
+
+    /// Seconds between reconciliations against the Polymarket data API (0 disables, live only)
+    #[serde(default = "default_reconcile_interval")]
+    pub reconcile_interval_secs: u64,
+
     /// Paper trading mode (no real orders)
     #[serde(default = "default_paper_trading")]
     pub paper_trading: bool,
@@ -66,6 +138,144 @@ pub struct Config {
     /// Minimum volume in last 24h to consider
     #[serde(default = "default_min_volume_24h")]
     pub min_volume_24h: f64,
+
+    /// Skip markets resolving sooner than this many days
+    #[serde(default)]
+    pub min_days_to_resolution: Option<f64>,
+
+    /// Skip markets resolving later than this many days (limits capital lockup)
+    #[serde(default)]
+    pub max_days_to_resolution: Option<f64>,
+
+    /// Trade markets that have no end date
+    #[serde(default = "default_allow_missing_end_date")]
+    pub allow_missing_end_date: bool,
+
+    /// Reuse the shared Gamma market snapshot while it is younger than this.
+    /// Keep it below `scan_interval_secs` so every cycle sees fresh markets and prices.
+    #[serde(default = "default_market_snapshot_ttl")]
+    pub market_snapshot_ttl_secs: u64,
+
+    /// Only scan markets under this Gamma tag ID
+    #[serde(default)]
+    pub market_tag_id: Option<String>,
+
+    /// Also scan markets under tags related to `market_tag_id`
+    #[serde(default)]
+    pub market_related_tags: bool,
+
+    /// Only scan markets that started within this many days
+    #[serde(default)]
+    pub max_market_age_days: Option<f64>,
+
+    /// Pages of 100 markets fetched for a full (longshot) snapshot
+    #[serde(default = "default_snapshot_max_pages")]
+    pub snapshot_max_pages: usize,
+
+    /// Rolling window of volume samples kept per market for spike detection
+    #[serde(default = "default_spike_window")]
+    pub spike_window_secs: u64,
+
+    /// Half-life of the EWMA volume-rate baseline
+    #[serde(default = "default_spike_halflife")]
+    pub spike_halflife_secs: u64,
+
+    /// Z-score of the volume rate above baseline that counts as a spike
+    #[serde(default = "default_spike_z_threshold")]
+    pub spike_z_threshold: f64,
+
+    /// Rate observations required before a market's baseline is trusted
+    #[serde(default = "default_spike_min_samples")]
+    pub spike_min_samples: usize,
+
+    /// File to persist volume history across restarts (unset = in-memory only)
+    #[serde(default)]
+    pub spike_history_path: Option<String>,
+
+    /// Fee rate in basis points charged on fills until the exchange reports one (paper trading)
+    #[serde(default)]
+    pub fee_rate_bps: f64,
+
+    /// Trading fee in basis points applied to arbitrage basket costs
+    #[serde(default)]
+    pub arbitrage_fee_bps: f64,
+
+    /// Minimum return (payout / cost - 1) for a YES + NO pair of a binary market
+    #[serde(default = "default_binary_arb_min_edge")]
+    pub binary_arb_min_edge: f64,
+
+    /// Minimum return (payout / cost - 1) for a neg-risk event basket
+    #[serde(default = "default_event_arb_min_edge")]
+    pub event_arb_min_edge: f64,
+
+    /// Skip neg-risk events with more open markets than this
+    #[serde(default = "default_event_arb_max_legs")]
+    pub event_arb_max_legs: usize,
+
+    /// Append-only JSON-lines log of placed trades and their fills (empty disables it)
+    #[serde(default = "default_trade_journal_path")]
+    pub trade_journal_path: Option<String>,
+
+    /// Trained probability model file (`train-model` writes it; empty disables the model)
+    #[serde(default = "default_probability_model_path")]
+    pub probability_model_path: Option<String>,
+
+    /// Skip opportunities whose model-implied return per dollar is below this
+    #[serde(default)]
+    pub min_expected_value: Option<f64>,
+
+    /// Resolved markets downloaded for training
+    #[serde(default = "default_model_train_markets")]
+    pub model_train_markets: usize,
+
+    /// Only train on markets that resolved within this many days
+    #[serde(default = "default_model_lookback_days")]
+    pub model_lookback_days: i64,
+
+    /// Highest entry price included in the training set
+    #[serde(default = "default_model_max_entry_price")]
+    pub model_max_entry_price: f64,
+
+    /// Per-cycle budget allocation across ranked opportunities
+    #[serde(default)]
+    pub allocation: AllocationConfig,
+
+    /// Scaled entries: resting bids stepped below the price instead of one order
+    #[serde(default)]
+    pub entry_ladder: EntryLadderConfig,
+
+    /// Stake sizing method and bankroll
+    #[serde(default)]
+    pub sizing: SizingConfig,
+
+    /// Opportunity scoring factors, weights and breakpoints
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+
+    /// Per-source overrides keyed by source name (new_markets, longshots, volume_spikes, mispriced, event_arbitrage)
+    #[serde(default)]
+    pub sources: HashMap<String, SourceConfig>,
+}
+
+/// Enable/schedule settings for a single opportunity source
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceConfig {
+    #[serde(default = "default_source_enabled")]
+    pub enabled: bool,
+
+    /// Seconds between runs (defaults to the source's own schedule)
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+}
+
+/// One step of the take-profit ladder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TakeProfitTier {
+    /// Price multiple of entry at which this tier sells
+    pub multiple: f64,
+    /// Fraction of the shares originally bought to sell; the rest of the position
+    /// is sold once the executed tiers add up to 1
+    pub sell_fraction: f64,
 }
 
 fn default_max_price_cents() -> u32 { 10 }
@@ -75,27 +285,94 @@ fn default_max_daily_spend() -> f64 { 100.0 }
 fn default_max_open_positions() -> usize { 50 }
 fn default_max_per_market() -> f64 { 20.0 }
 fn default_max_total_exposure() -> f64 { 500.0 }
+fn default_category_keyword_fallback() -> bool { true }
+fn default_book_filter_shortlist() -> usize { 20 }
+fn default_max_spread_cents() -> f64 { 3.0 }
+fn default_depth_ticks() -> u32 { 2 }
+fn default_max_depth_fraction() -> f64 { 0.25 }
 fn default_scan_interval() -> u64 { 30 }
 fn default_longshot_interval() -> u64 { 300 }
 fn default_auto_sell_multiplier() -> f64 { 3.0 }
 fn default_partial_sell_multiplier() -> f64 { 2.0 }
 fn default_paper_trading() -> bool { true }
+fn default_rpc_url() -> String { "https://polygon-rpc.com".to_string() }
+fn default_redeem_winnings() -> bool { true }
+fn default_reconcile_interval() -> u64 { 900 }
 fn default_min_volume_24h() -> f64 { 0.0 }
+fn default_allow_missing_end_date() -> bool { true }
+fn default_market_snapshot_ttl() -> u64 { 20 }
+fn default_snapshot_max_pages() -> usize { 20 }
+fn default_source_enabled() -> bool { true }
+fn default_execute_exits() -> bool { true }
+fn default_sell_order_ttl() -> u64 { 120 }
+fn default_trade_journal_path() -> Option<String> { Some("trades.jsonl".to_string()) }
+fn default_probability_model_path() -> Option<String> { Some("probability_model.json".to_string()) }
+fn default_model_train_markets() -> usize { 2000 }
+fn default_model_lookback_days() -> i64 { 365 }
+fn default_model_max_entry_price() -> f64 { 0.30 }
+fn default_binary_arb_min_edge() -> f64 { 0.02 }
+fn default_event_arb_min_edge() -> f64 { 0.01 }
+fn default_event_arb_max_legs() -> usize { 20 }
+fn default_spike_window() -> u64 { 6 * 3600 }
+fn default_spike_halflife() -> u64 { 3600 }
+fn default_spike_z_threshold() -> f64 { 3.0 }
+fn default_spike_min_samples() -> usize { 10 }
 
 impl Config {
     pub fn load() -> Self {
         let path = Path::new("config.json");
-        if path.exists() {
+        let mut config: Self = if path.exists() {
             let content = std::fs::read_to_string(path).unwrap_or_default();
             serde_json::from_str(&content).unwrap_or_default()
         } else {
             Self::default()
+        };
+        config.check_take_profit_ladder();
+        if config.market_snapshot_ttl_secs >= config.scan_interval_secs {
+            warn!("⚠️ market_snapshot_ttl_secs ({}s) is not below scan_interval_secs ({}s) - cycles will reuse stale markets",
+                config.market_snapshot_ttl_secs, config.scan_interval_secs);
+        }
+        config
+    }
+
+    /// Drop a take-profit ladder with negative fractions or fractions totalling over 1,
+    /// falling back to the legacy two-step exit
+    fn check_take_profit_ladder(&mut self) {
+        let fractions = self.take_profit_ladder.iter().map(|t| t.sell_fraction);
+        let invalid = fractions.clone().any(|f| !f.is_finite() || f < 0.0)
+            || fractions.sum::<f64>() > 1.0 + 1e-9
+            || self.take_profit_ladder.iter().any(|t| !t.multiple.is_finite() || t.multiple <= 0.0);
+        if invalid {
+            warn!("⚠️ Ignoring take_profit_ladder: sell fractions must be non-negative and add up to at most 1");
+            self.take_profit_ladder.clear();
         }
     }
 
     pub fn max_price_decimal(&self) -> f64 {
         self.max_price_cents as f64 / 100.0
     }
+
+    /// Take-profit ladder ordered by multiple, falling back to the legacy two-step exit
+    pub fn take_profit_tiers(&self) -> Vec<TakeProfitTier> {
+        let mut tiers = if self.take_profit_ladder.is_empty() {
+            vec![
+                TakeProfitTier { multiple: self.partial_sell_multiplier, sell_fraction: 0.5 },
+                TakeProfitTier { multiple: self.auto_sell_multiplier, sell_fraction: 1.0 },
+            ]
+        } else {
+            self.take_profit_ladder.clone()
+        };
+        tiers.sort_by(|a, b| a.multiple.total_cmp(&b.multiple));
+        tiers
+    }
+
+    /// Short description of the take-profit ladder (e.g. "50%@2x 100%@3x")
+    pub fn take_profit_label(&self) -> String {
+        self.take_profit_tiers().iter()
+            .map(|t| format!("{:.0}%@{}x", t.sell_fraction * 100.0, t.multiple))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl Default for Config {
@@ -109,14 +386,59 @@ impl Default for Config {
             max_per_market_usd: default_max_per_market(),
             max_total_exposure_usd: default_max_total_exposure(),
             categories: vec![],
+            category_keyword_fallback: default_category_keyword_fallback(),
+            book_filter_shortlist: default_book_filter_shortlist(),
+            max_spread_cents: default_max_spread_cents(),
+            depth_ticks: default_depth_ticks(),
+            max_depth_fraction: default_max_depth_fraction(),
             scan_interval_secs: default_scan_interval(),
             longshot_scan_interval_secs: default_longshot_interval(),
             auto_sell_multiplier: default_auto_sell_multiplier(),
             partial_sell_multiplier: default_partial_sell_multiplier(),
+            take_profit_ladder: vec![],
+            stop_loss_fraction: None,
+            trailing_stop_pct: None,
+            max_holding_hours: None,
+            exit_before_end_hours: None,
+            execute_exits: default_execute_exits(),
+            sell_order_ttl_secs: default_sell_order_ttl(),
+            rpc_url: default_rpc_url(),
+            redeem_winnings: default_redeem_winnings(),
+            funder_address: None,
+            reconcile_interval_secs: default_reconcile_interval(),
             paper_trading: default_paper_trading(),
             telegram_bot_token: String::new(),
             telegram_chat_id: String::new(),
             min_volume_24h: default_min_volume_24h(),
+            min_days_to_resolution: None,
+            max_days_to_resolution: None,
+            allow_missing_end_date: default_allow_missing_end_date(),
+            market_snapshot_ttl_secs: default_market_snapshot_ttl(),
+            market_tag_id: None,
+            market_related_tags: false,
+            max_market_age_days: None,
+            snapshot_max_pages: default_snapshot_max_pages(),
+            spike_window_secs: default_spike_window(),
+            spike_halflife_secs: default_spike_halflife(),
+            spike_z_threshold: default_spike_z_threshold(),
+            spike_min_samples: default_spike_min_samples(),
+            spike_history_path: None,
+            fee_rate_bps: 0.0,
+            arbitrage_fee_bps: 0.0,
+            binary_arb_min_edge: default_binary_arb_min_edge(),
+            event_arb_min_edge: default_event_arb_min_edge(),
+            event_arb_max_legs: default_event_arb_max_legs(),
+            trade_journal_path: default_trade_journal_path(),
+            probability_model_path: default_probability_model_path(),
+            min_expected_value: None,
+            model_train_markets: default_model_train_markets(),
+            model_lookback_days: default_model_lookback_days(),
+            model_max_entry_price: default_model_max_entry_price(),
+            allocation: AllocationConfig::default(),
+            entry_ladder: EntryLadderConfig::default(),
+            sizing: SizingConfig::default(),
+            scoring: ScoringConfig::default(),
+            sources: HashMap::new(),
         }
     }
 }
@@ -1,11 +1,15 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use tracing::{info, warn};
+use std::collections::HashMap;
+use tracing::{info, warn, debug};
 
-use crate::config::Config;
-use crate::scanner::MarketOpportunity;
-use crate::clob::{ClobClient, OrderSide};
+use crate::config::{Config, TakeProfitTier};
+use crate::ladder::{self, LadderIntent};
+use crate::positions::{ExitSignal, ExitType};
+use crate::scanner::{MarketOpportunity, OpportunityLeg};
+use crate::clob::{ApiCredentials, ClobClient, OrderSide};
+use crate::user_stream::{OrderEventKind, UserEvent};
 
 /// A trade record
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,15 +18,66 @@ pub struct Trade {
     pub condition_id: String,
     pub token_id: String,
     pub question: String,
+    /// Outcome label traded (e.g. "Yes", "Lakers")
     pub side: String,
+    #[serde(default)]
+    pub outcome_index: usize,
     pub price: f64,
     pub size: f64,           // number of shares
-    pub cost_usd: f64,       // total USDC spent
+    pub cost_usd: f64,       // total USDC spent (received, for sells)
+    #[serde(default)]
+    pub filled_size: f64,    // shares matched so far
+    /// USDC value of the matched shares at their fill prices
+    #[serde(default)]
+    pub filled_cost_usd: f64,
     pub status: TradeStatus,
     pub url: String,
     pub placed_at: String,
     pub filled_at: Option<String>,
     pub order_id: Option<String>,
+    /// Opportunity condition ID of the basket this trade is one leg of
+    #[serde(default)]
+    pub basket_id: Option<String>,
+    /// Guaranteed payout per share of the full basket
+    #[serde(default)]
+    pub basket_payout: Option<f64>,
+    #[serde(default)]
+    pub order_side: OrderSide,
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// Scheduled resolution of the market
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Entry ladder this trade is one rung of
+    #[serde(default)]
+    pub ladder_id: Option<String>,
+    /// Exchange fee rate charged on fills
+    #[serde(default)]
+    pub fee_rate_bps: f64,
+    /// Take-profit tiers a sell executes, marked sold once it fills
+    #[serde(default)]
+    pub take_profit_tiers: Vec<TakeProfitTier>,
+}
+
+impl Trade {
+    /// UTC day the order was placed, which is the day its capital was reserved
+    pub fn placed_on(&self) -> Option<NaiveDate> {
+        DateTime::parse_from_rfc3339(&self.placed_at).ok().map(|t| t.with_timezone(&Utc).date_naive())
+    }
+
+    /// Fee in USDC for `shares` filled at `price`, scaled by min(price, 1 - price) as the CLOB charges
+    pub fn fee_for(&self, shares: f64, price: f64) -> f64 {
+        self.fee_rate_bps / 10_000.0 * price.min(1.0 - price).max(0.0) * shares
+    }
+
+    /// Average price of the matched shares; the limit price for trades journaled before fill prices were kept
+    pub fn avg_fill_price(&self) -> f64 {
+        if self.filled_size > 0.0 && self.filled_cost_usd > 0.0 {
+            self.filled_cost_usd / self.filled_size
+        } else {
+            self.price
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -48,10 +103,27 @@ impl std::fmt::Display for TradeStatus {
     }
 }
 
+/// Result of applying a user channel event to a tracked order
+#[derive(Debug, Clone)]
+pub enum OrderUpdate {
+    /// New shares were matched for this order
+    Filled { trade: Trade, shares: f64, price: f64 },
+    /// Order was cancelled; `unfilled_usd` of reserved capital is released.
+    /// `nothing_filled` is set once the whole entry (order or ladder) ended without a fill.
+    Cancelled { trade: Trade, unfilled_usd: f64, nothing_filled: bool },
+    /// A match of `shares` at `price` failed on chain after being reported as filled.
+    /// The shares never changed hands and are no longer part of the order.
+    Reverted { trade: Trade, shares: f64, price: f64, nothing_filled: bool },
+}
+
 /// Executes trades on Polymarket CLOB
 pub struct Executor {
     trades: Vec<Trade>,
     clob_client: Option<ClobClient>,
+    /// Matched size per CLOB trade ID, keyed by our order ID
+    order_matches: HashMap<String, HashMap<String, f64>>,
+    /// Entry ladders with rungs still working
+    ladders: Vec<LadderIntent>,
 }
 
 impl Executor {
@@ -59,9 +131,21 @@ impl Executor {
         Self {
             trades: Vec::new(),
             clob_client: None,
+            order_matches: HashMap::new(),
+            ladders: Vec::new(),
         }
     }
 
+    /// API credentials of the live CLOB client, if authenticated
+    pub fn api_credentials(&self) -> Option<ApiCredentials> {
+        self.clob_client.as_ref().and_then(|c| c.credentials())
+    }
+
+    /// Wallet address of the live CLOB client
+    pub fn wallet_address(&self) -> Option<String> {
+        self.clob_client.as_ref().map(|c| c.address())
+    }
+
     /// Initialize live trading with CLOB client
     pub async fn init_live_trading(&mut self, private_key: &str) -> Result<()> {
         let mut client = ClobClient::new(private_key)?;
@@ -71,15 +155,16 @@ impl Executor {
         Ok(())
     }
 
-    /// Place a buy order for an opportunity
+    /// Place a buy order for an opportunity. An order the CLOB rejects is returned as an error
+    /// (and kept in the trade history as failed) so no budget is reserved for it.
     pub async fn place_buy_order(
         &mut self,
         opp: &MarketOpportunity,
         amount_usd: f64,
         config: &Config,
     ) -> Result<Trade> {
-        let buy_price = opp.yes_price.min(opp.no_price);
-        let side = if opp.yes_price <= opp.no_price { "YES" } else { "NO" };
+        let buy_price = opp.price;
+        let side = opp.outcome.as_str();
         let num_shares = amount_usd / buy_price;
 
         let trade_id = uuid::Uuid::new_v4().to_string();
@@ -92,14 +177,25 @@ impl Executor {
                 token_id: opp.token_id.clone(),
                 question: opp.question.clone(),
                 side: side.to_string(),
+                outcome_index: opp.outcome_index,
                 price: buy_price,
                 size: num_shares,
                 cost_usd: amount_usd,
+                filled_size: num_shares,
+                filled_cost_usd: num_shares * buy_price,
                 status: TradeStatus::PaperTrade,
                 url: opp.url.clone(),
                 placed_at: Utc::now().to_rfc3339(),
                 filled_at: Some(Utc::now().to_rfc3339()),
                 order_id: None,
+                basket_id: None,
+                basket_payout: None,
+                order_side: OrderSide::Buy,
+                neg_risk: opp.neg_risk,
+                end_date: opp.end_date.clone(),
+                ladder_id: None,
+                fee_rate_bps: config.fee_rate_bps,
+                take_profit_tiers: Vec::new(),
             };
 
             info!("📝 PAPER TRADE: {} {} @ ${:.4} ({:.0} shares, ${:.2})",
@@ -116,13 +212,8 @@ impl Executor {
         let clob = self.clob_client.as_ref()
             .ok_or_else(|| anyhow::anyhow!("CLOB client not initialized - set POLYMARKET_PRIVATE_KEY"))?;
 
-        let order_side = if opp.yes_price <= opp.no_price {
-            OrderSide::Buy
-        } else {
-            OrderSide::Buy // We always buy the cheaper side
-        };
-
-        match clob.place_limit_order(&opp.token_id, buy_price, num_shares, order_side, opp.neg_risk).await {
+        // Buy the chosen outcome's token
+        match clob.place_limit_order(&opp.token_id, buy_price, num_shares, OrderSide::Buy, opp.neg_risk).await {
             Ok(resp) => {
                 let status = if resp.success { TradeStatus::Pending } else { TradeStatus::Failed };
 
@@ -132,21 +223,34 @@ impl Executor {
                     token_id: opp.token_id.clone(),
                     question: opp.question.clone(),
                     side: side.to_string(),
+                    outcome_index: opp.outcome_index,
                     price: buy_price,
                     size: num_shares,
                     cost_usd: amount_usd,
+                    filled_size: 0.0,
+                    filled_cost_usd: 0.0,
                     status,
                     url: opp.url.clone(),
                     placed_at: Utc::now().to_rfc3339(),
                     filled_at: None,
                     order_id: if resp.success { Some(resp.order_id) } else { None },
+                    basket_id: None,
+                    basket_payout: None,
+                    order_side: OrderSide::Buy,
+                    neg_risk: opp.neg_risk,
+                    end_date: opp.end_date.clone(),
+                    ladder_id: None,
+                    fee_rate_bps: config.fee_rate_bps,
+                    take_profit_tiers: Vec::new(),
                 };
 
+                self.trades.push(trade.clone());
                 if !resp.success {
-                    warn!("💀 Order failed: {:?}", resp.error_msg);
+                    // Rejected orders never rest on the book, so they must not reserve any budget
+                    let reason = resp.error_msg.unwrap_or_default();
+                    warn!("💀 Order failed: {}", reason);
+                    anyhow::bail!("Order rejected by the CLOB: {}", reason);
                 }
-
-                self.trades.push(trade.clone());
                 Ok(trade)
             }
             Err(e) => {
@@ -157,14 +261,25 @@ impl Executor {
                     token_id: opp.token_id.clone(),
                     question: opp.question.clone(),
                     side: side.to_string(),
+                    outcome_index: opp.outcome_index,
                     price: buy_price,
                     size: num_shares,
                     cost_usd: amount_usd,
+                    filled_size: 0.0,
+                    filled_cost_usd: 0.0,
                     status: TradeStatus::Failed,
                     url: opp.url.clone(),
                     placed_at: Utc::now().to_rfc3339(),
                     filled_at: None,
                     order_id: None,
+                    basket_id: None,
+                    basket_payout: None,
+                    order_side: OrderSide::Buy,
+                    neg_risk: opp.neg_risk,
+                    end_date: opp.end_date.clone(),
+                    ladder_id: None,
+                    fee_rate_bps: config.fee_rate_bps,
+                    take_profit_tiers: Vec::new(),
                 };
                 self.trades.push(trade.clone());
                 Err(e)
@@ -172,14 +287,539 @@ impl Executor {
         }
     }
 
-    /// Get all trades
-    pub fn trades(&self) -> &[Trade] {
-        &self.trades
+    /// Buy an equal number of shares of every leg of a basket opportunity.
+    /// In live mode, legs already on the book are cancelled if a later leg fails.
+    pub async fn place_basket_order(
+        &mut self,
+        opp: &MarketOpportunity,
+        amount_usd: f64,
+        config: &Config,
+    ) -> Result<Vec<Trade>> {
+        let num_shares = (amount_usd / opp.price).min(opp.max_shares.unwrap_or(f64::INFINITY));
+        let paper = config.paper_trading;
+
+        info!("{} BASKET ORDER: {} - {} legs @ ${:.4} ({:.0} shares, ${:.2})",
+            if paper { "📝" } else { "🔥" }, opp.question, opp.legs.len(), opp.price, num_shares, amount_usd);
+
+        let leg_trade = |leg: &OpportunityLeg, status: TradeStatus, order_id: Option<String>| Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            condition_id: leg.condition_id.clone(),
+            token_id: leg.token_id.clone(),
+            question: leg.question.clone(),
+            side: leg.outcome.clone(),
+            outcome_index: leg.outcome_index,
+            price: leg.price,
+            size: num_shares,
+            cost_usd: num_shares * leg.price,
+            filled_size: if paper { num_shares } else { 0.0 },
+            filled_cost_usd: if paper { num_shares * leg.price } else { 0.0 },
+            status,
+            url: opp.url.clone(),
+            placed_at: Utc::now().to_rfc3339(),
+            filled_at: if paper { Some(Utc::now().to_rfc3339()) } else { None },
+            order_id,
+            basket_id: Some(opp.condition_id.clone()),
+            basket_payout: opp.guaranteed_payout,
+            order_side: OrderSide::Buy,
+            neg_risk: leg.neg_risk,
+            end_date: opp.end_date.clone(),
+            ladder_id: None,
+            fee_rate_bps: config.fee_rate_bps,
+            take_profit_tiers: Vec::new(),
+        };
+
+        if paper {
+            let trades: Vec<Trade> = opp.legs.iter()
+                .map(|leg| leg_trade(leg, TradeStatus::PaperTrade, None))
+                .collect();
+            self.trades.extend(trades.iter().cloned());
+            return Ok(trades);
+        }
+
+        let clob = self.clob_client.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("CLOB client not initialized - set POLYMARKET_PRIVATE_KEY"))?;
+
+        let mut placed = Vec::new();
+        for leg in &opp.legs {
+            let result = clob.place_limit_order(&leg.token_id, leg.price, num_shares, OrderSide::Buy, leg.neg_risk).await;
+            match result {
+                Ok(resp) if resp.success => {
+                    placed.push(leg_trade(leg, TradeStatus::Pending, Some(resp.order_id)));
+                }
+                failure => {
+                    let reason = match failure {
+                        Ok(resp) => resp.error_msg.unwrap_or_default(),
+                        Err(e) => e.to_string(),
+                    };
+                    warn!("💀 Basket leg failed ({} {}): {} - cancelling {} placed legs",
+                        leg.outcome, leg.question, reason, placed.len());
+
+                    for trade in &mut placed {
+                        let order_id = trade.order_id.clone().unwrap_or_default();
+                        match clob.cancel_order(&order_id).await {
+                            Ok(true) => trade.status = TradeStatus::Cancelled,
+                            Ok(false) | Err(_) => warn!("⚠️ Failed to cancel basket leg order {}", order_id),
+                        }
+                    }
+                    // Legs that could not be cancelled stay tracked so their fills are recorded
+                    self.trades.extend(placed.into_iter().filter(|t| t.status != TradeStatus::Cancelled));
+                    anyhow::bail!("Basket order failed on leg {}: {}", leg.question, reason);
+                }
+            }
+        }
+
+        self.trades.extend(placed.iter().cloned());
+        Ok(placed)
+    }
+
+    /// Split a buy into resting bids stepped below the opportunity price on the market's
+    /// `tick_size`, tracked as one ladder. Paper rungs rest until `simulate_paper_fills`
+    /// sees the book trade through them.
+    pub async fn place_ladder_order(
+        &mut self,
+        opp: &MarketOpportunity,
+        amount_usd: f64,
+        tick_size: f64,
+        config: &Config,
+    ) -> Result<Vec<Trade>> {
+        let prices = ladder::rung_prices(opp.price, tick_size, &config.entry_ladder);
+        if prices.is_empty() {
+            anyhow::bail!("No ladder rungs above zero for {}", opp.question);
+        }
+        let paper = config.paper_trading;
+        let rung_usd = amount_usd / prices.len() as f64;
+        let ladder_id = uuid::Uuid::new_v4().to_string();
+
+        info!("{} LADDER ORDER: {} {} - {} rungs ${:.4}-${:.4} (${:.2})",
+            if paper { "📝" } else { "🔥" }, opp.outcome, opp.question, prices.len(),
+            prices[0], prices[prices.len() - 1], amount_usd);
+
+        let mut placed = Vec::new();
+        for price in prices {
+            let shares = rung_usd / price;
+            let order_id = if paper {
+                None
+            } else {
+                let clob = self.clob_client.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("CLOB client not initialized - set POLYMARKET_PRIVATE_KEY"))?;
+                match clob.place_limit_order(&opp.token_id, price, shares, OrderSide::Buy, opp.neg_risk).await {
+                    Ok(resp) if resp.success => Some(resp.order_id),
+                    Ok(resp) => {
+                        warn!("💀 Ladder rung @ ${:.4} rejected: {:?}", price, resp.error_msg);
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("💀 Ladder rung @ ${:.4} failed: {}", price, e);
+                        continue;
+                    }
+                }
+            };
+
+            placed.push(Trade {
+                id: uuid::Uuid::new_v4().to_string(),
+                condition_id: opp.condition_id.clone(),
+                token_id: opp.token_id.clone(),
+                question: opp.question.clone(),
+                side: opp.outcome.clone(),
+                outcome_index: opp.outcome_index,
+                price,
+                size: shares,
+                cost_usd: rung_usd,
+                filled_size: 0.0,
+                filled_cost_usd: 0.0,
+                status: TradeStatus::Pending,
+                url: opp.url.clone(),
+                placed_at: Utc::now().to_rfc3339(),
+                filled_at: None,
+                order_id,
+                basket_id: None,
+                basket_payout: None,
+                order_side: OrderSide::Buy,
+                neg_risk: opp.neg_risk,
+                end_date: opp.end_date.clone(),
+                ladder_id: Some(ladder_id.clone()),
+                fee_rate_bps: config.fee_rate_bps,
+                take_profit_tiers: Vec::new(),
+            });
+        }
+
+        if placed.is_empty() {
+            anyhow::bail!("Every ladder rung failed for {}", opp.question);
+        }
+
+        self.ladders.push(LadderIntent {
+            id: ladder_id,
+            question: opp.question.clone(),
+            target_shares: amount_usd / opp.price,
+            expires_at: Utc::now() + chrono::Duration::minutes(config.entry_ladder.ttl_minutes),
+        });
+        self.trades.extend(placed.iter().cloned());
+        Ok(placed)
+    }
+
+    /// Tokens with paper ladder rungs resting on the book
+    pub fn paper_ladder_tokens(&self) -> Vec<String> {
+        let mut tokens: Vec<String> = self.trades.iter()
+            .filter(|t| t.ladder_id.is_some() && t.order_id.is_none() && t.status == TradeStatus::Pending)
+            .map(|t| t.token_id.clone())
+            .collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+        tokens
+    }
+
+    /// Fill paper ladder rungs in full wherever the best ask from `asks` (token_id, price) has
+    /// come down to the rung price
+    pub fn simulate_paper_fills(&mut self, asks: &[(String, f64)]) -> Vec<OrderUpdate> {
+        let fillable: Vec<usize> = self.trades.iter().enumerate()
+            .filter(|(_, t)| t.ladder_id.is_some() && t.order_id.is_none() && t.status == TradeStatus::Pending)
+            .filter(|(_, t)| asks.iter().any(|(token, ask)| *token == t.token_id && *ask <= t.price))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        fillable.into_iter()
+            .filter_map(|idx| {
+                let (size, price) = (self.trades[idx].size, self.trades[idx].price);
+                self.apply_matched(idx, size, price)
+            })
+            .collect()
+    }
+
+    /// Cancel the working rungs of ladders that reached their target shares or expired.
+    /// Paper cancellations are returned directly; live ones arrive on the user channel.
+    pub async fn manage_ladders(&mut self) -> Vec<OrderUpdate> {
+        let now = Utc::now();
+        let mut updates = Vec::new();
+
+        for intent in std::mem::take(&mut self.ladders) {
+            let rungs: Vec<usize> = self.trades.iter().enumerate()
+                .filter(|(_, t)| t.ladder_id.as_deref() == Some(intent.id.as_str()))
+                .map(|(idx, _)| idx)
+                .collect();
+            let filled: f64 = rungs.iter().map(|&idx| self.trades[idx].filled_size).sum();
+            let working: Vec<usize> = rungs.into_iter()
+                .filter(|&idx| matches!(self.trades[idx].status, TradeStatus::Pending | TradeStatus::PartialFill))
+                .collect();
+
+            let target_reached = filled >= intent.target_shares - 1e-6;
+            if working.is_empty() {
+                continue;
+            }
+            if !target_reached && now < intent.expires_at {
+                self.ladders.push(intent);
+                continue;
+            }
+
+            info!("🪜 Ladder {} {}: {:.0}/{:.0} shares - cancelling {} rungs",
+                intent.question, if target_reached { "filled to target" } else { "expired" },
+                filled, intent.target_shares, working.len());
+
+            // Any rung not confirmed cancelling keeps the ladder queued for another attempt
+            let mut retry = false;
+            for idx in working {
+                match self.trades[idx].order_id.clone() {
+                    Some(order_id) => {
+                        let Some(clob) = self.clob_client.as_ref() else {
+                            retry = true;
+                            continue;
+                        };
+                        match clob.cancel_order(&order_id).await {
+                            Ok(true) => debug!("Cancel requested for ladder rung {}", order_id),
+                            Ok(false) | Err(_) => {
+                                warn!("⚠️ Failed to cancel ladder rung order {} - retrying next cycle", order_id);
+                                retry = true;
+                            }
+                        }
+                    }
+                    None => {
+                        let trade = &mut self.trades[idx];
+                        let unfilled_usd = (trade.size - trade.filled_size).max(0.0) * trade.price;
+                        trade.status = if trade.filled_size > 0.0 { TradeStatus::PartialFill } else { TradeStatus::Cancelled };
+                        let trade = trade.clone();
+                        updates.push(OrderUpdate::Cancelled {
+                            nothing_filled: self.nothing_filled(&trade),
+                            unfilled_usd,
+                            trade,
+                        });
+                    }
+                }
+            }
+            if retry {
+                self.ladders.push(intent);
+            }
+        }
+
+        updates
+    }
+
+    /// Cancel every order still working on a token whose market has settled: sells with
+    /// nothing left to sell and ladder rungs for a market that no longer trades.
+    /// Paper cancellations are returned directly; live ones arrive on the user channel.
+    pub async fn cancel_token_orders(&mut self, token_id: &str) -> Vec<OrderUpdate> {
+        let working: Vec<usize> = self.trades.iter().enumerate()
+            .filter(|(_, t)| t.token_id == token_id && matches!(t.status, TradeStatus::Pending | TradeStatus::PartialFill))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut updates = Vec::new();
+        for idx in working {
+            match self.trades[idx].order_id.clone() {
+                Some(order_id) => {
+                    let Some(clob) = self.clob_client.as_ref() else { continue };
+                    match clob.cancel_order(&order_id).await {
+                        Ok(true) => info!("❌ Cancelling order {} on settled market {}", order_id, self.trades[idx].question),
+                        Ok(false) | Err(_) => warn!("⚠️ Failed to cancel order {} on settled market", order_id),
+                    }
+                }
+                None => {
+                    let trade = &mut self.trades[idx];
+                    let unfilled_usd = (trade.size - trade.filled_size).max(0.0) * trade.price;
+                    trade.status = if trade.filled_size > 0.0 { TradeStatus::PartialFill } else { TradeStatus::Cancelled };
+                    let trade = trade.clone();
+                    updates.push(OrderUpdate::Cancelled {
+                        nothing_filled: self.nothing_filled(&trade),
+                        unfilled_usd,
+                        trade,
+                    });
+                }
+            }
+        }
+        updates
+    }
+
+    /// Whether the entry a cancelled order belongs to ended without any shares: false for
+    /// basket legs (the basket is released as a whole), every rung of a ladder otherwise
+    fn nothing_filled(&self, trade: &Trade) -> bool {
+        if trade.basket_id.is_some() {
+            return false;
+        }
+        match &trade.ladder_id {
+            Some(ladder_id) => self.trades.iter()
+                .filter(|t| t.ladder_id.as_ref() == Some(ladder_id))
+                .all(|t| t.filled_size <= 0.0 && t.status != TradeStatus::Pending),
+            None => trade.filled_size <= 0.0,
+        }
+    }
+
+    /// Sell shares of a position to act on an exit signal, limit-priced at `price`
+    pub async fn place_sell_order(&mut self, signal: &ExitSignal, price: f64, config: &Config) -> Result<Trade> {
+        let shares = signal.shares_to_sell;
+        let paper = config.paper_trading;
+
+        let mut trade = Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            condition_id: signal.condition_id.clone(),
+            token_id: signal.token_id.clone(),
+            question: signal.question.clone(),
+            side: signal.side.clone(),
+            outcome_index: signal.outcome_index,
+            price,
+            size: shares,
+            cost_usd: shares * price,
+            filled_size: if paper { shares } else { 0.0 },
+            filled_cost_usd: if paper { shares * price } else { 0.0 },
+            status: if paper { TradeStatus::PaperTrade } else { TradeStatus::Pending },
+            url: signal.url.clone(),
+            placed_at: Utc::now().to_rfc3339(),
+            filled_at: if paper { Some(Utc::now().to_rfc3339()) } else { None },
+            order_id: None,
+            basket_id: None,
+            basket_payout: None,
+            order_side: OrderSide::Sell,
+            neg_risk: signal.neg_risk,
+            end_date: None,
+            ladder_id: None,
+            fee_rate_bps: config.fee_rate_bps,
+            take_profit_tiers: match &signal.signal_type {
+                ExitType::TakeProfit { tiers, .. } => tiers.clone(),
+                _ => Vec::new(),
+            },
+        };
+
+        info!("{} SELL ({}): {} {} @ ${:.4} ({:.0} shares)",
+            if paper { "📝" } else { "🔥" }, signal.signal_type, signal.side, signal.question, price, shares);
+
+        if !paper {
+            let clob = self.clob_client.as_ref()
+                .ok_or_else(|| anyhow::anyhow!("CLOB client not initialized - set POLYMARKET_PRIVATE_KEY"))?;
+            let resp = clob.place_limit_order(&signal.token_id, price, shares, OrderSide::Sell, signal.neg_risk).await?;
+            if !resp.success {
+                anyhow::bail!("Sell order rejected: {}", resp.error_msg.unwrap_or_default());
+            }
+            trade.order_id = Some(resp.order_id);
+        }
+
+        self.trades.push(trade.clone());
+        Ok(trade)
+    }
+
+    /// Request cancellation of live sells resting longer than `ttl_secs`, so their exits can be
+    /// re-placed at a fresh price. Cancellations arrive on the user channel.
+    pub async fn cancel_stale_sells(&self, ttl_secs: u64) {
+        let Some(clob) = self.clob_client.as_ref() else { return };
+        let cutoff = Utc::now() - chrono::Duration::seconds(ttl_secs as i64);
+        let stale = self.trades.iter().filter(|t| {
+            t.order_side == OrderSide::Sell
+                && matches!(t.status, TradeStatus::Pending | TradeStatus::PartialFill)
+                && DateTime::parse_from_rfc3339(&t.placed_at).is_ok_and(|placed| placed < cutoff)
+        });
+
+        for trade in stale {
+            let Some(order_id) = &trade.order_id else { continue };
+            match clob.cancel_order(order_id).await {
+                Ok(true) => info!("⌛ Cancelling unfilled sell for {} after {}s", trade.question, ttl_secs),
+                Ok(false) | Err(_) => warn!("⚠️ Failed to cancel stale sell order {}", order_id),
+            }
+        }
+    }
+
+    /// Whether a live sell order for this token is still working
+    pub fn has_open_sell(&self, token_id: &str) -> bool {
+        self.trades.iter().any(|t| {
+            t.order_side == OrderSide::Sell
+                && t.token_id == token_id
+                && matches!(t.status, TradeStatus::Pending | TradeStatus::PartialFill)
+        })
+    }
+
+    /// Apply a user channel event to the matching order, returning any fill or cancellation
+    pub fn apply_user_event(&mut self, event: &UserEvent) -> Vec<OrderUpdate> {
+        let mut updates = Vec::new();
+
+        match event {
+            UserEvent::Order { order_id, asset_id, kind, original_size, size_matched, price } => {
+                let Some(idx) = self.find_order(order_id) else {
+                    debug!("Ignoring order event for unknown order {}", order_id);
+                    return updates;
+                };
+
+                match kind {
+                    OrderEventKind::Placement => {
+                        info!("📥 Order live on book: {} ({:.0} shares of {} @ ${:.4})",
+                            order_id, original_size, asset_id, price);
+                    }
+                    OrderEventKind::Update => {
+                        updates.extend(self.apply_matched(idx, *size_matched, *price));
+                    }
+                    OrderEventKind::Cancellation => {
+                        updates.extend(self.apply_matched(idx, *size_matched, *price));
+                        let trade = &mut self.trades[idx];
+                        let unfilled_shares = (trade.size - trade.filled_size).max(0.0);
+                        trade.status = if trade.filled_size > 0.0 { TradeStatus::PartialFill } else { TradeStatus::Cancelled };
+                        info!("❌ Order cancelled: {} ({:.0}/{:.0} shares filled)", order_id, trade.filled_size, trade.size);
+                        let trade = trade.clone();
+                        updates.push(OrderUpdate::Cancelled {
+                            nothing_filled: self.nothing_filled(&trade),
+                            unfilled_usd: unfilled_shares * trade.price,
+                            trade,
+                        });
+                    }
+                }
+            }
+            UserEvent::Trade { trade_id, status, fills } => {
+                if status == "FAILED" {
+                    warn!("💀 CLOB trade {} failed on-chain", trade_id);
+                    for fill in fills {
+                        updates.extend(self.revert_match(&fill.order_id, trade_id, fill.price));
+                    }
+                    return updates;
+                }
+
+                for fill in fills {
+                    let Some(idx) = self.find_order(&fill.order_id) else { continue };
+                    if let Some(fee_rate_bps) = fill.fee_rate_bps {
+                        self.trades[idx].fee_rate_bps = fee_rate_bps;
+                    }
+                    let matches = self.order_matches.entry(fill.order_id.clone()).or_default();
+                    matches.insert(trade_id.clone(), fill.matched_size);
+                    let total_matched: f64 = matches.values().sum();
+                    debug!("Trade {} ({}) matched {:.2} shares of {}", trade_id, status, fill.matched_size, fill.asset_id);
+                    updates.extend(self.apply_matched(idx, total_matched, fill.price));
+                }
+            }
+        }
+
+        updates
+    }
+
+    /// Raise an order's filled size to `total_matched`, returning the newly filled shares.
+    /// Order updates and trade events both report cumulative matches, so taking the
+    /// maximum avoids double-counting a fill seen on both.
+    fn apply_matched(&mut self, idx: usize, total_matched: f64, price: f64) -> Option<OrderUpdate> {
+        let trade = &mut self.trades[idx];
+        let total_matched = total_matched.min(trade.size);
+        let new_shares = total_matched - trade.filled_size;
+        if new_shares <= 1e-9 {
+            return None;
+        }
+
+        let fill_price = if price > 0.0 { price } else { trade.price };
+        trade.filled_size = total_matched;
+        trade.filled_cost_usd += new_shares * fill_price;
+        if trade.filled_size >= trade.size - 1e-6 {
+            trade.status = TradeStatus::Filled;
+            trade.filled_at = Some(Utc::now().to_rfc3339());
+        } else {
+            trade.status = TradeStatus::PartialFill;
+        }
+
+        info!("✅ Fill: {} {} +{:.0} shares @ ${:.4} ({:.0}/{:.0})",
+            trade.side, trade.question, new_shares, fill_price, trade.filled_size, trade.size);
+
+        Some(OrderUpdate::Filled { trade: trade.clone(), shares: new_shares, price: fill_price })
+    }
+
+    /// Drop a failed CLOB trade from an order's matches, taking its shares back out of the
+    /// filled size. The failed shares leave the order, so its size shrinks with them.
+    fn revert_match(&mut self, order_id: &str, trade_id: &str, price: f64) -> Option<OrderUpdate> {
+        let idx = self.find_order(order_id)?;
+        let matches = self.order_matches.get_mut(order_id)?;
+        matches.remove(trade_id)?;
+        let total_matched: f64 = matches.values().sum();
+
+        let trade = &mut self.trades[idx];
+        let shares = trade.filled_size - total_matched.min(trade.filled_size);
+        if shares <= 1e-9 {
+            return None;
+        }
+        let was_open = trade.status != TradeStatus::Filled;
+        let price = if price > 0.0 { price } else { trade.price };
+        trade.filled_size -= shares;
+        trade.filled_cost_usd = (trade.filled_cost_usd - shares * price).max(0.0);
+        trade.size -= shares;
+        trade.cost_usd = (trade.cost_usd - shares * trade.price).max(0.0);
+        trade.filled_at = None;
+        trade.status = match (was_open, trade.filled_size > 0.0) {
+            (_, true) => TradeStatus::PartialFill,
+            (true, false) => TradeStatus::Pending,
+            (false, false) => TradeStatus::Failed,
+        };
+
+        warn!("💀 Reverted fill: {} {} -{:.0} shares @ ${:.4} ({:.0}/{:.0})",
+            trade.side, trade.question, shares, price, trade.filled_size, trade.size);
+        let trade = trade.clone();
+        Some(OrderUpdate::Reverted {
+            nothing_filled: !was_open && self.nothing_filled(&trade),
+            trade,
+            shares,
+            price,
+        })
+    }
+
+    fn find_order(&self, order_id: &str) -> Option<usize> {
+        self.trades.iter().position(|t| t.order_id.as_deref() == Some(order_id))
+    }
+
+    /// Buy orders still waiting to fill
+    pub fn open_buy_orders(&self) -> impl Iterator<Item = &Trade> + '_ {
+        self.trades.iter()
+            .filter(|t| t.order_side == OrderSide::Buy && matches!(t.status, TradeStatus::Pending | TradeStatus::PartialFill))
     }
 
-    /// Get trades by status
-    pub fn trades_by_status(&self, status: TradeStatus) -> Vec<&Trade> {
-        self.trades.iter().filter(|t| t.status == status).collect()
+    /// Condition IDs (and basket IDs) of live orders still waiting to fill
+    pub fn open_order_ids(&self) -> impl Iterator<Item = String> + '_ {
+        self.open_buy_orders()
+            .flat_map(|t| std::iter::once(t.condition_id.clone()).chain(t.basket_id.clone()))
     }
 
     /// Count total trades placed today
@@ -192,7 +832,7 @@ impl Executor {
     pub fn spent_today(&self) -> f64 {
         let today = Utc::now().format("%Y-%m-%d").to_string();
         self.trades.iter()
-            .filter(|t| t.placed_at.starts_with(&today))
+            .filter(|t| t.order_side == OrderSide::Buy && t.placed_at.starts_with(&today))
             .map(|t| t.cost_usd)
             .sum()
     }
@@ -0,0 +1,519 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, debug};
+
+use crate::config::Config;
+
+const GAMMA_URL: &str = "https://gamma-api.polymarket.com";
+/// Gamma's maximum page size for the markets endpoint
+const PAGE_SIZE: u32 = 100;
+/// Pages of the volume-ordered universe fetched on a regular (non-full) cycle
+const HEAD_PAGES: usize = 2;
+/// Pages of volume-ordered events fetched when a source needs event groupings
+const EVENT_PAGES: usize = 2;
+
+/// Raw market data from Gamma API
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct GammaMarket {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub slug: Option<String>,
+    #[serde(rename = "conditionId", default)]
+    pub condition_id: String,
+    #[serde(rename = "outcomePrices", default)]
+    pub outcome_prices: Option<String>,
+    #[serde(default)]
+    pub outcomes: Option<String>,
+    #[serde(default)]
+    pub liquidity: Option<String>,
+    #[serde(default)]
+    pub volume: Option<String>,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub resolved: Option<bool>,
+    #[serde(rename = "endDateIso", default)]
+    pub end_date_iso: Option<String>,
+    #[serde(rename = "endDate", default)]
+    pub end_date: Option<String>,
+    #[serde(rename = "createdAt", default)]
+    pub created_at: Option<String>,
+    #[serde(rename = "volume24hr", default)]
+    pub volume_24hr: Option<f64>,
+    #[serde(rename = "clobTokenIds", default)]
+    pub clob_token_ids: Option<String>,
+    #[serde(default)]
+    pub events: Vec<GammaEvent>,
+    #[serde(rename = "acceptingOrders", default)]
+    pub accepting_orders: bool,
+    #[serde(rename = "negRisk", default)]
+    pub neg_risk: bool,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<GammaTag>,
+    /// Best ask for the first (YES) outcome
+    #[serde(rename = "bestAsk", default)]
+    pub best_ask: Option<f64>,
+    /// Best bid for the first (YES) outcome
+    #[serde(rename = "bestBid", default)]
+    pub best_bid: Option<f64>,
+    /// When the market actually closed (e.g. `2024-11-06 05:43:29+00`)
+    #[serde(rename = "closedTime", default)]
+    pub closed_time: Option<String>,
+    /// Member label within its event (e.g. "Lakers", "Other")
+    #[serde(rename = "groupItemTitle", default)]
+    pub group_item_title: Option<String>,
+}
+
+impl GammaMarket {
+    /// Open, unresolved and accepting orders
+    pub fn is_tradeable(&self) -> bool {
+        !self.closed && !self.resolved.unwrap_or(false) && self.accepting_orders
+    }
+
+    /// Outcome prices parsed from the JSON-encoded `outcomePrices` string
+    pub fn parsed_prices(&self) -> Option<Vec<f64>> {
+        let prices: Vec<String> = serde_json::from_str(self.outcome_prices.as_ref()?).ok()?;
+        prices.iter().map(|p| p.parse().ok()).collect()
+    }
+
+    /// Payout per share of each outcome once the market has closed and settled: 1/0 for a
+    /// clear winner, or the split (e.g. 0.5/0.5) of a tie or invalid market
+    pub fn final_payouts(&self) -> Option<Vec<f64>> {
+        if !self.closed {
+            return None;
+        }
+        let prices = self.parsed_prices()?;
+        let total: f64 = prices.iter().sum();
+        let settled_split = (total - 1.0).abs() <= 0.02
+            && prices.iter().all(|p| *p <= 0.01 || *p >= 0.99 || (p - 0.5).abs() <= 0.01);
+        if !settled_split && self.resolved != Some(true) {
+            return None;
+        }
+        // Snap last-trade noise to the payout grid
+        Some(prices.iter().map(|p| (p * 100.0).round() / 100.0).collect())
+    }
+
+    /// Index of the winning outcome once the market has closed with final 1/0 prices
+    pub fn resolved_outcome(&self) -> Option<usize> {
+        let payouts = self.final_payouts()?;
+        let winner = payouts.iter().position(|p| *p >= 0.99)?;
+        payouts.iter().enumerate().all(|(i, p)| i == winner || *p <= 0.01).then_some(winner)
+    }
+
+    /// CLOB token IDs per outcome, parsed from the JSON-encoded `clobTokenIds` string
+    pub fn token_ids(&self) -> Vec<String> {
+        self.clob_token_ids.as_ref()
+            .and_then(|ids| serde_json::from_str(ids).ok())
+            .unwrap_or_default()
+    }
+
+    /// Outcome labels (e.g. `["Yes", "No"]`, `["Lakers", "Celtics"]`), padded to `count`
+    pub fn outcome_labels(&self, count: usize) -> Vec<String> {
+        let mut labels: Vec<String> = self.outcomes.as_ref()
+            .and_then(|o| serde_json::from_str(o).ok())
+            .unwrap_or_default();
+        for i in labels.len()..count {
+            labels.push(match i {
+                0 => "Yes".to_string(),
+                1 => "No".to_string(),
+                _ => format!("Outcome {}", i + 1),
+            });
+        }
+        labels
+    }
+
+    /// Scheduled resolution time, from the full `endDate` timestamp or the `endDateIso` date
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        self.end_date.as_deref()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.with_timezone(&Utc))
+            .or_else(|| {
+                let date = chrono::NaiveDate::parse_from_str(self.end_date_iso.as_deref()?, "%Y-%m-%d").ok()?;
+                Some(date.and_hms_opt(23, 59, 59)?.and_utc())
+            })
+    }
+
+    /// Actual close time, which can be well before or after the scheduled end
+    pub fn closed_at(&self) -> Option<DateTime<Utc>> {
+        let closed = self.closed_time.as_deref()?;
+        DateTime::parse_from_rfc3339(closed)
+            .or_else(|_| DateTime::parse_from_str(closed, "%Y-%m-%d %H:%M:%S%#z"))
+            .ok()
+            .map(|d| d.with_timezone(&Utc))
+    }
+
+    /// Primary category: the market's own, else its event's, else its first tag
+    pub fn primary_category(&self) -> String {
+        let non_empty = |c: &Option<String>| c.clone().filter(|c| !c.is_empty());
+        non_empty(&self.category)
+            .or_else(|| self.events.iter().find_map(|e| non_empty(&e.category)))
+            .or_else(|| self.all_tags().find_map(|t| non_empty(&t.label)))
+            .unwrap_or_default()
+    }
+
+    /// Tag labels and slugs from the market and its events, lowercased and deduplicated
+    pub fn tag_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.all_tags()
+            .flat_map(|t| [t.label.clone(), t.slug.clone()])
+            .flatten()
+            .chain(self.category.clone())
+            .chain(self.events.iter().filter_map(|e| e.category.clone()))
+            .map(|n| n.trim().to_lowercase())
+            .filter(|n| !n.is_empty())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn all_tags(&self) -> impl Iterator<Item = &GammaTag> {
+        self.tags.iter().chain(self.events.iter().flat_map(|e| e.tags.iter()))
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct GammaEvent {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+    #[serde(rename = "negRisk", default)]
+    pub neg_risk: bool,
+    /// Members can still be added, so the listed ones need not cover every outcome
+    #[serde(rename = "negRiskAugmented", default)]
+    pub neg_risk_augmented: bool,
+    #[serde(default)]
+    pub closed: bool,
+    /// Member markets (populated by the `/events` endpoint)
+    #[serde(default)]
+    pub markets: Vec<GammaMarket>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<GammaTag>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct GammaTag {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+}
+
+/// Typed query for the Gamma `/markets` and `/events` endpoints
+#[derive(Debug, Clone, Default)]
+pub struct MarketQuery {
+    limit: Option<u32>,
+    offset: Option<u32>,
+    order: Option<String>,
+    ascending: Option<bool>,
+    closed: Option<bool>,
+    tag_id: Option<String>,
+    related_tags: bool,
+    include_tag: bool,
+    start_date_min: Option<DateTime<Utc>>,
+    start_date_max: Option<DateTime<Utc>>,
+    end_date_min: Option<DateTime<Utc>>,
+    end_date_max: Option<DateTime<Utc>>,
+    condition_ids: Vec<String>,
+}
+
+impl MarketQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sort field (e.g. `volume24hr`, `createdAt`) and direction
+    pub fn order(mut self, field: &str, ascending: bool) -> Self {
+        self.order = Some(field.to_string());
+        self.ascending = Some(ascending);
+        self
+    }
+
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = Some(closed);
+        self
+    }
+
+    /// Restrict to a Gamma tag, optionally including related tags
+    pub fn tag(mut self, tag_id: &str, related_tags: bool) -> Self {
+        self.tag_id = Some(tag_id.to_string());
+        self.related_tags = related_tags;
+        self
+    }
+
+    /// Include tag metadata on each returned market
+    pub fn include_tags(mut self) -> Self {
+        self.include_tag = true;
+        self
+    }
+
+    pub fn start_date_range(mut self, min: Option<DateTime<Utc>>, max: Option<DateTime<Utc>>) -> Self {
+        self.start_date_min = min;
+        self.start_date_max = max;
+        self
+    }
+
+    pub fn end_date_range(mut self, min: Option<DateTime<Utc>>, max: Option<DateTime<Utc>>) -> Self {
+        self.end_date_min = min;
+        self.end_date_max = max;
+        self
+    }
+
+    pub fn condition_ids<S: AsRef<str>>(mut self, ids: &[S]) -> Self {
+        self.condition_ids = ids.iter().map(|id| id.as_ref().to_string()).collect();
+        self
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(limit) = self.limit { params.push(("limit", limit.to_string())); }
+        if let Some(offset) = self.offset { params.push(("offset", offset.to_string())); }
+        if let Some(order) = &self.order { params.push(("order", order.clone())); }
+        if let Some(ascending) = self.ascending { params.push(("ascending", ascending.to_string())); }
+        if let Some(closed) = self.closed { params.push(("closed", closed.to_string())); }
+        if let Some(tag_id) = &self.tag_id {
+            params.push(("tag_id", tag_id.clone()));
+            if self.related_tags { params.push(("related_tags", "true".to_string())); }
+        }
+        if self.include_tag { params.push(("include_tag", "true".to_string())); }
+        if let Some(d) = self.start_date_min { params.push(("start_date_min", d.to_rfc3339())); }
+        if let Some(d) = self.start_date_max { params.push(("start_date_max", d.to_rfc3339())); }
+        if let Some(d) = self.end_date_min { params.push(("end_date_min", d.to_rfc3339())); }
+        if let Some(d) = self.end_date_max { params.push(("end_date_max", d.to_rfc3339())); }
+        for cid in &self.condition_ids { params.push(("condition_ids", cid.clone())); }
+        params
+    }
+}
+
+/// The market universe fetched once per cycle and shared by every scanner
+#[derive(Debug, Default)]
+pub struct MarketSnapshot {
+    pub fetched_at: DateTime<Utc>,
+    /// Most recently created open markets (newest first)
+    pub recent: Vec<GammaMarket>,
+    /// Open markets ordered by 24h volume (highest first)
+    pub by_volume: Vec<GammaMarket>,
+    /// Whether `by_volume` covers the full paged universe or only the head
+    pub full: bool,
+    /// Open events with their member markets (fetched only when a source needs them)
+    pub events: Vec<GammaEvent>,
+    pub has_events: bool,
+}
+
+impl MarketSnapshot {
+    /// The `n` highest-volume markets
+    pub fn top_by_volume(&self, n: usize) -> &[GammaMarket] {
+        &self.by_volume[..n.min(self.by_volume.len())]
+    }
+
+    pub fn age_secs(&self) -> u64 {
+        Utc::now().signed_duration_since(self.fetched_at).num_seconds().max(0) as u64
+    }
+}
+
+/// Gamma API client with a TTL-cached market snapshot
+pub struct GammaClient {
+    client: reqwest::Client,
+    snapshot: Option<Arc<MarketSnapshot>>,
+}
+
+impl GammaClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+            snapshot: None,
+        }
+    }
+
+    /// Fetch a single page of markets
+    pub async fn fetch_markets(&self, query: &MarketQuery) -> Result<Vec<GammaMarket>> {
+        let response = self.client
+            .get(format!("{}/markets", GAMMA_URL))
+            .query(&query.params())
+            .send()
+            .await?;
+        if response.status() != 200 {
+            anyhow::bail!("Gamma API returned {}", response.status());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Fetch a single page of events with their markets
+    pub async fn fetch_events(&self, query: &MarketQuery) -> Result<Vec<GammaEvent>> {
+        let response = self.client
+            .get(format!("{}/events", GAMMA_URL))
+            .query(&query.params())
+            .send()
+            .await?;
+        if response.status() != 200 {
+            anyhow::bail!("Gamma API returned {}", response.status());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Fetch up to `max_pages` consecutive pages of a query
+    pub async fn fetch_pages(&self, query: &MarketQuery, max_pages: usize) -> Result<Vec<GammaMarket>> {
+        let mut markets = Vec::new();
+
+        for page in 0..max_pages {
+            let page_query = query.clone().limit(PAGE_SIZE).offset(page as u32 * PAGE_SIZE);
+            let batch = self.fetch_markets(&page_query).await?;
+            let batch_len = batch.len();
+            debug!("Gamma page {} - {} markets", page + 1, batch_len);
+            markets.extend(batch);
+
+            if batch_len < PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok(markets)
+    }
+
+    /// Get the cycle's market snapshot, reusing the cached one while it is younger than
+    /// `market_snapshot_ttl_secs` (and deep enough / carrying events when requested)
+    pub async fn snapshot(&mut self, config: &Config, full: bool, with_events: bool) -> Result<Arc<MarketSnapshot>> {
+        if let Some(cached) = &self.snapshot {
+            if cached.age_secs() < config.market_snapshot_ttl_secs
+                && (cached.full || !full)
+                && (cached.has_events || !with_events)
+            {
+                debug!("Reusing market snapshot ({}s old)", cached.age_secs());
+                return Ok(cached.clone());
+            }
+        }
+
+        let universe = universe_query(config);
+        let recent_query = universe.clone().include_tags().order("createdAt", false).limit(PAGE_SIZE);
+        let recent = self.fetch_markets(&recent_query).await?;
+
+        let volume_query = universe.clone().include_tags().order("volume24hr", false);
+        let pages = if full { config.snapshot_max_pages } else { HEAD_PAGES };
+        let by_volume = self.fetch_pages(&volume_query, pages).await?;
+
+        let mut events = Vec::new();
+        if with_events {
+            let event_query = universe.order("volume24hr", false);
+            for page in 0..EVENT_PAGES {
+                let page_query = event_query.clone().limit(PAGE_SIZE).offset(page as u32 * PAGE_SIZE);
+                let batch = self.fetch_events(&page_query).await?;
+                let batch_len = batch.len();
+                events.extend(batch);
+                if batch_len < PAGE_SIZE as usize {
+                    break;
+                }
+            }
+        }
+
+        info!("📊 Market snapshot: {} recent, {} by volume{}, {} events",
+            recent.len(), by_volume.len(), if full { " (full)" } else { "" }, events.len());
+
+        let snapshot = Arc::new(MarketSnapshot {
+            fetched_at: Utc::now(),
+            recent,
+            by_volume,
+            full,
+            events,
+            has_events: with_events,
+        });
+        self.snapshot = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+}
+
+/// Open markets, narrowed to `market_tag_id` and `max_market_age_days` when configured
+fn universe_query(config: &Config) -> MarketQuery {
+    let mut query = MarketQuery::new().closed(false);
+    if let Some(tag_id) = config.market_tag_id.as_deref().filter(|t| !t.is_empty()) {
+        query = query.tag(tag_id, config.market_related_tags);
+    }
+    if let Some(days) = config.max_market_age_days {
+        let since = Utc::now() - chrono::Duration::seconds((days * 86_400.0) as i64);
+        query = query.start_date_range(Some(since), None);
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn query_params_cover_every_filter() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 6, 30, 12, 0, 0).unwrap();
+        let query = MarketQuery::new()
+            .closed(false)
+            .tag("21", true)
+            .include_tags()
+            .start_date_range(Some(start), None)
+            .end_date_range(None, Some(end))
+            .order("volume24hr", false)
+            .limit(100)
+            .offset(200);
+
+        let params: Vec<(&str, String)> = query.params();
+        let expected = [
+            ("limit", "100"),
+            ("offset", "200"),
+            ("order", "volume24hr"),
+            ("ascending", "false"),
+            ("closed", "false"),
+            ("tag_id", "21"),
+            ("related_tags", "true"),
+            ("include_tag", "true"),
+            ("start_date_min", "2026-01-01T00:00:00+00:00"),
+            ("end_date_max", "2026-06-30T12:00:00+00:00"),
+        ];
+        assert_eq!(params, expected.map(|(k, v)| (k, v.to_string())));
+    }
+
+    #[test]
+    fn tag_without_related_tags_omits_the_flag() {
+        let params = MarketQuery::new().tag("100", false).condition_ids(&["0xa", "0xb"]).params();
+        assert_eq!(params, vec![
+            ("tag_id", "100".to_string()),
+            ("condition_ids", "0xa".to_string()),
+            ("condition_ids", "0xb".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn universe_query_applies_configured_filters() {
+        let config = Config { market_tag_id: Some("2".into()), max_market_age_days: Some(7.0), ..Config::default() };
+        let params = universe_query(&config).params();
+        assert!(params.contains(&("tag_id", "2".to_string())));
+        assert!(!params.iter().any(|(k, _)| *k == "related_tags"));
+        assert!(params.iter().any(|(k, _)| *k == "start_date_min"));
+
+        let params = universe_query(&Config::default()).params();
+        assert_eq!(params, vec![("closed", "false".to_string())]);
+    }
+}
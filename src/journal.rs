@@ -0,0 +1,91 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::executor::Trade;
+use crate::positions::ExitType;
+use crate::scanner::MarketOpportunity;
+
+/// Why a trade was taken, captured when it is placed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryContext {
+    pub category: String,
+    /// Primary discovery type key (e.g. `longshot`)
+    pub discovery_type: String,
+    pub reasons: Vec<String>,
+    pub score: f64,
+    #[serde(default)]
+    pub predicted_probability: Option<f64>,
+}
+
+/// One line of the trade journal: a trade's state at placement or after a fill/cancel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub recorded_at: DateTime<Utc>,
+    pub trade: Trade,
+    /// Present on the placement entry only
+    #[serde(default)]
+    pub context: Option<EntryContext>,
+    /// Exit rule that triggered a sell
+    #[serde(default)]
+    pub exit_reason: Option<String>,
+}
+
+/// Append-only JSON-lines log of every trade and its later updates
+pub struct TradeJournal {
+    path: Option<PathBuf>,
+}
+
+impl TradeJournal {
+    pub fn new(path: Option<&str>) -> Self {
+        Self { path: path.filter(|p| !p.is_empty()).map(PathBuf::from) }
+    }
+
+    /// Record a newly placed trade with the opportunity that triggered it
+    pub fn record_placement(&self, trade: &Trade, opp: &MarketOpportunity) {
+        let context = EntryContext {
+            category: opp.category.clone(),
+            discovery_type: opp.discovery_type.key().to_string(),
+            reasons: opp.reasons.iter().map(|r| r.key().to_string()).collect(),
+            score: opp.score,
+            predicted_probability: opp.predicted_probability,
+        };
+        self.append(trade, Some(context), None);
+    }
+
+    /// Record a sell placed to act on an exit signal
+    pub fn record_exit(&self, trade: &Trade, reason: &ExitType) {
+        self.append(trade, None, Some(reason.key().to_string()));
+    }
+
+    /// Record a trade's updated fill or cancellation state
+    pub fn record_update(&self, trade: &Trade) {
+        self.append(trade, None, None);
+    }
+
+    fn append(&self, trade: &Trade, context: Option<EntryContext>, exit_reason: Option<String>) {
+        let Some(path) = &self.path else { return };
+        let entry = JournalEntry { recorded_at: Utc::now(), trade: trade.clone(), context, exit_reason };
+        let result = serde_json::to_string(&entry).map_err(anyhow::Error::from).and_then(|line| {
+            let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)?;
+            Ok(())
+        });
+        if let Err(e) = result {
+            warn!("Failed to write trade journal: {}", e);
+        }
+    }
+
+    /// Read every entry, skipping lines that fail to parse
+    pub fn load(path: &Path) -> Result<Vec<JournalEntry>> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trade journal {}", path.display()))?;
+        Ok(content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
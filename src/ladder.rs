@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Scaled entry settings: split each buy into resting bids stepped below the price
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntryLadderConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Bids per ladder, the first one step below the opportunity price
+    #[serde(default = "default_rungs")]
+    pub rungs: usize,
+
+    /// Price step between rungs in cents
+    #[serde(default = "default_step_cents")]
+    pub step_cents: f64,
+
+    /// Unfilled rungs are cancelled after this many minutes
+    #[serde(default = "default_ttl_minutes")]
+    pub ttl_minutes: i64,
+}
+
+fn default_rungs() -> usize { 3 }
+fn default_step_cents() -> f64 { 1.0 }
+fn default_ttl_minutes() -> i64 { 240 }
+
+impl Default for EntryLadderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rungs: default_rungs(),
+            step_cents: default_step_cents(),
+            ttl_minutes: default_ttl_minutes(),
+        }
+    }
+}
+
+/// Parent intent of one ladder's rung orders
+#[derive(Debug, Clone)]
+pub struct LadderIntent {
+    pub id: String,
+    pub question: String,
+    /// Shares a single order at the opportunity price would have bought
+    pub target_shares: f64,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Rung prices stepping down from one step below `price`, rounded down to the market's
+/// `tick_size` and dropping any that reach zero or collapse onto the same tick
+pub fn rung_prices(price: f64, tick_size: f64, config: &EntryLadderConfig) -> Vec<f64> {
+    let tick = if tick_size > 0.0 { tick_size } else { 0.01 };
+    let mut prices: Vec<f64> = (1..=config.rungs.max(1))
+        .map(|i| {
+            let ticks = ((price - i as f64 * config.step_cents / 100.0) / tick + 1e-9).floor();
+            (ticks * tick * 1e6).round() / 1e6
+        })
+        .filter(|p| *p > 0.0 && *p < price)
+        .collect();
+    prices.dedup();
+    prices
+}
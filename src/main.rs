@@ -1,23 +1,52 @@
 mod config;
+mod gamma;
 mod scanner;
+mod scoring;
+mod sizing;
+mod allocator;
+mod ladder;
+mod sources;
 mod strategy;
 mod risk;
 mod executor;
 mod positions;
 mod telegram;
+mod volume_history;
 mod clob;
+mod chain;
+mod journal;
+mod calibration;
+mod model;
+mod orderbook;
+mod reconcile;
+mod user_stream;
 
 use anyhow::Result;
-use tracing::{info, warn, error};
+use std::collections::HashSet;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn, error};
 use tracing_subscriber::EnvFilter;
 
 use config::Config;
-use scanner::Scanner;
+use scanner::{MarketOpportunity, Scanner};
+use journal::TradeJournal;
+use sizing::Bankroll;
+use allocator::CycleAllocator;
+use model::ModelStore;
+use orderbook::OrderBookClient;
+use reconcile::Reconciler;
+use sources::SourceRegistry;
 use strategy::Strategy;
 use risk::RiskManager;
-use executor::Executor;
+use clob::OrderSide;
+use chain::Redeemer;
+use executor::{Executor, OrderUpdate, Trade, TradeStatus};
 use positions::PositionTracker;
 use telegram::TelegramNotifier;
+use user_stream::{UserEvent, UserStream};
+
+/// Held markets closed this long without settling are flagged once
+const UNSETTLED_WARN_HOURS: f64 = 48.0;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -35,6 +64,17 @@ async fn main() -> Result<()> {
 
     // Load config
     let config = Config::load();
+
+    // Offline subcommands
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("train-model") => return model::train(&config).await,
+        Some("calibration-report") => {
+            let csv_path = args.get(2).map(String::as_str).unwrap_or("calibration.csv");
+            return calibration::report(&config, csv_path).await;
+        }
+        _ => {}
+    }
     let mode = if config.paper_trading { "📝 PAPER TRADING" } else { "🔥 LIVE TRADING" };
 
     println!("╔══════════════════════════════════════════════════╗");
@@ -44,17 +84,27 @@ async fn main() -> Result<()> {
     println!("║ Max/trade: ${:<39.2} ║", config.max_per_trade_usd);
     println!("║ Max/day: ${:<41.2} ║", config.max_daily_spend_usd);
     println!("║ Buy price: ≤ {}¢{:>38} ║", config.max_price_cents, "");
-    println!("║ Auto-sell: {}x entry{:>33} ║", config.auto_sell_multiplier, "");
+    println!("║ Take-profit: {:<35} ║", config.take_profit_label());
     println!("║ Scan: {}s new | {}s full{:>28} ║", config.scan_interval_secs, config.longshot_scan_interval_secs, "");
     println!("╚══════════════════════════════════════════════════╝");
     println!();
 
     // Initialize components
     let mut scanner = Scanner::new();
+    let mut sources = SourceRegistry::new(&config);
+    let order_books = OrderBookClient::new();
+    let mut models = ModelStore::new();
+    let journal = TradeJournal::new(config.trade_journal_path.as_deref());
     let mut risk_manager = RiskManager::new();
     let mut executor = Executor::new();
     let mut position_tracker = PositionTracker::new();
     let notifier = TelegramNotifier::new(&config);
+    let mut reconciler = Reconciler::new();
+
+    // Fill and order events from the authenticated user channel (live trading only)
+    let mut user_events: Option<mpsc::UnboundedReceiver<UserEvent>> = None;
+    // On-chain redemption of winning positions (live trading only)
+    let mut redeemer: Option<Redeemer> = None;
 
     // Initialize CLOB client for live trading
     if !config.paper_trading {
@@ -66,7 +116,25 @@ async fn main() -> Result<()> {
             return Ok(());
         }
         match executor.init_live_trading(&private_key).await {
-            Ok(_) => info!("🔥 CLOB client authenticated - live trading ready"),
+            Ok(_) => {
+                // Orders, redemptions and reconciliation all use the trading key's wallet
+                let wallet = executor.wallet_address().unwrap_or_default();
+                if let Some(funder) = config.funder_address.as_deref().filter(|f| !f.eq_ignore_ascii_case(&wallet)) {
+                    error!("❌ funder_address {} is not the trading key's wallet {}", funder, wallet);
+                    error!("   Proxy wallets are not supported - remove funder_address or use that wallet's key");
+                    return Ok(());
+                }
+                info!("🔥 CLOB client authenticated - live trading ready");
+                if let Some(creds) = executor.api_credentials() {
+                    user_events = Some(UserStream::spawn(creds));
+                }
+                if config.redeem_winnings {
+                    match Redeemer::connect(&config.rpc_url, &private_key).await {
+                        Ok(client) => redeemer = Some(client),
+                        Err(e) => warn!("⚠️ Redemption disabled - failed to connect to {}: {}", config.rpc_url, e),
+                    }
+                }
+            }
             Err(e) => {
                 error!("❌ Failed to initialize live trading: {}", e);
                 error!("   Falling back to paper trading mode");
@@ -83,6 +151,9 @@ async fn main() -> Result<()> {
     }
 
     let mut cycle = 0u64;
+    // The daily summary goes out on the first cycle of each new UTC day
+    let mut summary_day = chrono::Utc::now().date_naive();
+    let mut warned_unsettled: HashSet<String> = HashSet::new();
 
     loop {
         cycle += 1;
@@ -91,40 +162,28 @@ async fn main() -> Result<()> {
         // Reload config each cycle for hot-reloading
         let config = Config::load();
 
-        // Step 1: Scan for opportunities
+        // Step 1: Run due opportunity sources on one shared market snapshot
         let mut all_opportunities = Vec::new();
+        let due = sources.due(&config);
 
-        // Always scan new markets
-        match scanner.scan_new_markets(&config).await {
-            Ok(opps) => all_opportunities.extend(opps),
-            Err(e) => warn!("New market scan error: {}", e),
-        }
-
-        // Full longshot scan periodically
-        if scanner.needs_full_scan(&config) {
-            match scanner.scan_longshots(&config).await {
-                Ok(opps) => all_opportunities.extend(opps),
-                Err(e) => warn!("Longshot scan error: {}", e),
+        if !due.is_empty() {
+            match scanner.refresh_snapshot(&config, sources.needs_full_snapshot(&due), sources.needs_events(&due)).await {
+                Ok(snapshot) => all_opportunities = sources.run(&due, &snapshot, &config).await,
+                Err(e) => warn!("Market snapshot error: {}", e),
             }
         }
 
-        // Volume spike scan
-        match scanner.scan_volume_spikes(&config).await {
-            Ok(opps) => all_opportunities.extend(opps),
-            Err(e) => warn!("Volume spike scan error: {}", e),
-        }
-
-        // Mispriced market scan (every full scan cycle)
-        if scanner.needs_full_scan(&config) {
-            match scanner.scan_mispriced(&config).await {
-                Ok(opps) => all_opportunities.extend(opps),
-                Err(e) => warn!("Mispriced scan error: {}", e),
-            }
+        if let Some(model) = models.current(&config) {
+            model.annotate(&mut all_opportunities);
         }
 
         // Step 2: Filter through strategy
-        let existing_positions = position_tracker.position_ids();
-        let filtered = Strategy::filter_opportunities(all_opportunities, &config, &existing_positions);
+        // Markets already held or with a live order, extended as this cycle trades
+        let mut held: HashSet<String> = position_tracker.position_ids().into_iter()
+            .chain(executor.open_order_ids())
+            .collect();
+        let ranked = Strategy::filter_opportunities(all_opportunities, &config, &held);
+        let filtered = Strategy::filter_by_order_book(ranked, &config, &order_books).await;
 
         if !filtered.is_empty() {
             info!("🎯 {} tradeable opportunities found", filtered.len());
@@ -135,58 +194,257 @@ async fn main() -> Result<()> {
             }
         }
 
-        // Step 3: Execute trades through risk manager
-        for opp in &filtered {
-            let trade_amount = config.max_per_trade_usd;
+        // Step 3: Size the ranked opportunities, split this cycle's budget among the top ones,
+        // then execute each allocation through the risk manager
+        let bankroll = Bankroll {
+            equity: config.sizing.bankroll_usd + position_tracker.total_pnl(),
+            exposure: risk_manager.total_exposure(),
+        };
+        let candidates: Vec<&MarketOpportunity> = filtered.iter()
+            .filter(|opp| !held.contains(&opp.condition_id) && !opp.legs.iter().any(|l| held.contains(&l.condition_id)))
+            .collect();
+        let stakes: Vec<f64> = candidates.iter()
+            .map(|opp| {
+                let stake = sizing::stake(opp, &config, &bankroll);
+                // Book-sized opportunities can't absorb more than their matched depth
+                opp.max_shares.map_or(stake, |shares| stake.min(shares * opp.price))
+            })
+            .collect();
+        let budget = allocator::cycle_budget(&config, risk_manager.daily_spent(), risk_manager.remaining_budget(&config));
+        let mut allocation = CycleAllocator::new(&stakes, budget, &config);
+        if !candidates.is_empty() {
+            info!("💰 Allocating ${:.2} across up to {} of {} candidates",
+                budget, config.allocation.max_trades_per_cycle, candidates.len());
+        }
+
+        // Walk the ranked list until the slots or the budget run out; candidates that are
+        // skipped, blocked or fail to place leave their share to the next in line
+        for (opp, &stake) in candidates.iter().zip(&stakes) {
+            if allocation.is_done() {
+                break;
+            }
+            let Some(trade_amount) = allocation.amount_for(stake) else { continue };
+            if held.contains(&opp.condition_id) || opp.legs.iter().any(|l| held.contains(&l.condition_id)) {
+                info!("⏭️ Skipping {} - already traded this cycle", opp.question);
+                continue;
+            }
+
+            let committed = match risk_manager.check_trade(opp, trade_amount, &config) {
+                Ok(approved_amount) if opp.is_basket() => {
+                    match executor.place_basket_order(opp, approved_amount, &config).await {
+                        Ok(trades) => {
+                            risk_manager.record_trade(&opp.condition_id, approved_amount);
+                            held.insert(opp.condition_id.clone());
+                            held.extend(opp.legs.iter().map(|l| l.condition_id.clone()));
+                            for trade in &trades {
+                                journal.record_placement(trade, opp);
+                            }
+
+                            if trades.iter().all(|t| t.status == TradeStatus::PaperTrade) {
+                                for trade in &trades {
+                                    position_tracker.add_from_trade(trade);
+                                }
 
-            match risk_manager.check_trade(opp, trade_amount, &config) {
+                                if let Err(e) = notifier.send_basket(&trades, opp).await {
+                                    warn!("Failed to send basket notification: {}", e);
+                                }
+                            }
+                            Some(approved_amount)
+                        }
+                        Err(e) => {
+                            warn!("Failed to place basket: {}", e);
+                            None
+                        }
+                    }
+                }
+                Ok(approved_amount) if config.entry_ladder.enabled => {
+                    // Rungs sit on the market's tick grid; 1¢ unless the book says otherwise
+                    let tick_size = order_books.fetch(&opp.token_id).await.map_or(0.01, |book| book.tick_size);
+                    match executor.place_ladder_order(opp, approved_amount, tick_size, &config).await {
+                        Ok(rungs) => {
+                            // Capital for every rung stays reserved until it fills or is cancelled
+                            let reserved: f64 = rungs.iter().map(|t| t.cost_usd).sum();
+                            risk_manager.record_trade(&opp.condition_id, reserved);
+                            held.insert(opp.condition_id.clone());
+                            for rung in &rungs {
+                                journal.record_placement(rung, opp);
+                            }
+                            Some(reserved)
+                        }
+                        Err(e) => {
+                            warn!("Failed to place ladder: {}", e);
+                            None
+                        }
+                    }
+                }
                 Ok(approved_amount) => {
                     match executor.place_buy_order(opp, approved_amount, &config).await {
                         Ok(trade) => {
                             risk_manager.record_trade(&opp.condition_id, approved_amount);
-                            position_tracker.add_from_trade(&trade);
+                            held.insert(opp.condition_id.clone());
+                            journal.record_placement(&trade, opp);
 
-                            if let Err(e) = notifier.send_trade(&trade, opp).await {
-                                warn!("Failed to send trade notification: {}", e);
+                            // Live orders become positions as fills arrive on the user channel
+                            if trade.status == TradeStatus::PaperTrade {
+                                position_tracker.add_from_trade(&trade);
+
+                                if let Err(e) = notifier.send_trade(&trade, opp).await {
+                                    warn!("Failed to send trade notification: {}", e);
+                                }
                             }
+                            Some(approved_amount)
                         }
                         Err(e) => {
                             warn!("Failed to place trade: {}", e);
+                            None
                         }
                     }
                 }
                 Err(e) => {
-                    info!("⛔ Trade blocked by risk manager: {}", e);
-                    break; // Stop trading this cycle if risk limits hit
+                    // A per-market limit only rules out this market; keep going down the list
+                    info!("⛔ Trade blocked by risk manager: {} - skipping {}", e, opp.question);
+                    None
                 }
+            };
+
+            if let Some(spent) = committed {
+                allocation.commit(spent);
             }
         }
 
-        // Step 4: Update position prices from Gamma API
-        let position_ids = position_tracker.position_ids();
-        if !position_ids.is_empty() {
-            match scanner.fetch_current_prices(&position_ids).await {
-                Ok(price_updates) => {
-                    if !price_updates.is_empty() {
-                        info!("📡 Updated prices for {} positions", price_updates.len());
-                        position_tracker.update_prices(&price_updates);
+        // Step 4: Update position prices from Gamma API and settle resolved markets
+        if !position_tracker.positions().is_empty() {
+            match scanner.fetch_current_prices(position_tracker.positions()).await {
+                Ok(updates) => {
+                    if !updates.prices.is_empty() {
+                        info!("📡 Updated prices for {} positions", updates.prices.len());
+                        position_tracker.update_prices(&updates.prices);
+                    }
+
+                    for (token_id, hours) in &updates.unsettled {
+                        if *hours >= UNSETTLED_WARN_HOURS && warned_unsettled.insert(token_id.clone()) {
+                            warn!("⚠️ Market of held token {} closed {:.0}h ago and has not settled", token_id, hours);
+                        }
+                    }
+
+                    for (token_id, payout_price) in &updates.resolved {
+                        // Nothing can trade on a settled market; pull any sells or rungs still working
+                        for update in executor.cancel_token_orders(token_id).await {
+                            handle_order_update(update, &mut risk_manager, &mut position_tracker, &notifier, &journal).await;
+                        }
+                        let Some(settlement) = position_tracker.settle(token_id, *payout_price) else { continue };
+                        let pos = &settlement.position;
+                        // Basket capital is reserved under the basket ID until its last leg settles
+                        match (&pos.basket_id, &settlement.basket) {
+                            (Some(basket_id), None) => risk_manager.reduce_exposure(basket_id, pos.cost_usd),
+                            (Some(basket_id), Some(_)) => risk_manager.record_close(basket_id, pos.cost_usd),
+                            (None, _) => risk_manager.record_close(&pos.condition_id, pos.cost_usd),
+                        }
+
+                        if let Err(e) = notifier.send_settlement(&settlement).await {
+                            warn!("Failed to send settlement notification: {}", e);
+                        }
+                        if settlement.payout_price > 0.0 {
+                            if let Some(redeemer) = redeemer.as_mut() {
+                                redeemer.queue(&settlement.position);
+                            }
+                        }
                     }
                 }
                 Err(e) => warn!("Failed to fetch position prices: {}", e),
             }
         }
 
-        // Step 5: Check for exit signals
+        // Redeem won positions on chain and confirm earlier redemptions
+        if let Some(redeemer) = redeemer.as_mut() {
+            for redeemed in redeemer.process().await {
+                if let Err(e) = notifier.send_redemption(&redeemed).await {
+                    warn!("Failed to send redemption notification: {}", e);
+                }
+            }
+        }
+
+        // Step 4b: Work entry ladders - paper rungs fill when the book trades through them,
+        // and rungs left once a ladder fills to target or expires are cancelled
+        let mut ladder_updates = Vec::new();
+        let paper_tokens = executor.paper_ladder_tokens();
+        if !paper_tokens.is_empty() {
+            let books = futures::future::join_all(paper_tokens.iter().map(|token| order_books.fetch(token))).await;
+            let asks: Vec<(String, f64)> = paper_tokens.into_iter().zip(books)
+                .filter_map(|(token, book)| Some((token, book.ok()?.asks.first()?.price)))
+                .collect();
+            ladder_updates.extend(executor.simulate_paper_fills(&asks));
+        }
+        ladder_updates.extend(executor.manage_ladders().await);
+        for update in ladder_updates {
+            handle_order_update(update, &mut risk_manager, &mut position_tracker, &notifier, &journal).await;
+        }
+
+        // Step 5: Check for exit signals and sell. Sells left unfilled past their TTL are
+        // cancelled first so the exit is re-placed at the current price.
+        if config.execute_exits {
+            executor.cancel_stale_sells(config.sell_order_ttl_secs).await;
+        }
         let exit_signals = position_tracker.check_exits(&config);
         for signal in &exit_signals {
             info!("🎯 Exit signal: {} {} @ ${:.4} (entry ${:.4}, {:+.1}%)",
                 signal.signal_type, signal.question, signal.current_price, signal.entry_price, signal.pnl_pct);
 
-            if let Err(e) = notifier.send_exit_signal(signal).await {
+            if executor.has_open_sell(&signal.token_id) {
+                debug!("Sell already working for {}", signal.question);
+                continue;
+            }
+
+            let mut placed = false;
+            if config.execute_exits {
+                // Protective exits sell into the best bid; the others rest at the current price
+                let price = if signal.signal_type.is_protective() {
+                    match order_books.fetch(&signal.token_id).await {
+                        Ok(book) => book.bids.first().map(|l| l.price),
+                        Err(e) => {
+                            warn!("Failed to fetch book for {}: {}", signal.question, e);
+                            None
+                        }
+                    }
+                } else {
+                    Some(signal.current_price)
+                };
+
+                match price {
+                    None => warn!("No bid to sell {} into - retrying next cycle", signal.question),
+                    Some(price) => match executor.place_sell_order(signal, price, &config).await {
+                        Ok(trade) => {
+                            placed = true;
+                            journal.record_exit(&trade, &signal.signal_type);
+                            // Live sells reduce the position (and mark take-profit tiers) as fills arrive on the user channel
+                            if trade.status == TradeStatus::PaperTrade {
+                                position_tracker.mark_tiers_executed(&trade.token_id, &trade.take_profit_tiers);
+                                apply_sell(&trade, trade.size, trade.price, &mut risk_manager, &mut position_tracker);
+                            }
+                        }
+                        Err(e) => warn!("Failed to place sell for {}: {}", signal.question, e),
+                    },
+                }
+            }
+
+            if let Err(e) = notifier.send_exit_signal(signal, placed).await {
                 warn!("Failed to send exit notification: {}", e);
             }
         }
 
+        // Step 5b: Reconcile holdings against the trading wallet (live only)
+        if let Some(address) = executor.wallet_address().filter(|_| !config.paper_trading && reconciler.is_due(&config)) {
+            match reconciler.run(&address, &config, &mut position_tracker, &executor, &mut risk_manager).await {
+                Ok(discrepancies) if !discrepancies.is_empty() => {
+                    if let Err(e) = notifier.send_reconciliation(&discrepancies).await {
+                        warn!("Failed to send reconciliation notification: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to reconcile holdings: {}", e),
+            }
+        }
+
         // Step 6: Log status
         let risk_summary = risk_manager.summary(&config);
         let portfolio_summary = position_tracker.summary();
@@ -195,8 +453,108 @@ async fn main() -> Result<()> {
         info!("📊 Trades today: {} | Total spent: ${:.2}",
             executor.trades_today(), executor.spent_today());
 
-        // Wait for next scan
+        let today = chrono::Utc::now().date_naive();
+        if today != summary_day {
+            summary_day = today;
+            if let Err(e) = notifier.send_daily_summary(&portfolio_summary, &risk_summary).await {
+                warn!("Failed to send daily summary: {}", e);
+            }
+        }
+
+        // Wait for next scan, applying user channel events as they arrive
         info!("⏳ Next scan in {}s...\n", config.scan_interval_secs);
-        tokio::time::sleep(tokio::time::Duration::from_secs(config.scan_interval_secs)).await;
+        let next_scan = tokio::time::sleep(tokio::time::Duration::from_secs(config.scan_interval_secs));
+        tokio::pin!(next_scan);
+        loop {
+            let event = match user_events.as_mut() {
+                Some(rx) => tokio::select! {
+                    _ = &mut next_scan => break,
+                    event = rx.recv() => event,
+                },
+                None => {
+                    (&mut next_scan).await;
+                    break;
+                }
+            };
+
+            match event {
+                Some(event) => {
+                    for update in executor.apply_user_event(&event) {
+                        handle_order_update(update, &mut risk_manager, &mut position_tracker, &notifier, &journal).await;
+                    }
+                }
+                None => {
+                    warn!("⚠️ User channel task stopped - fills will no longer be tracked");
+                    user_events = None;
+                }
+            }
+        }
+    }
+}
+
+/// Apply a fill or cancellation from the user channel to positions and risk limits
+async fn handle_order_update(
+    update: OrderUpdate,
+    risk_manager: &mut RiskManager,
+    position_tracker: &mut PositionTracker,
+    notifier: &TelegramNotifier,
+    journal: &TradeJournal,
+) {
+    match update {
+        OrderUpdate::Filled { trade, shares, price } if trade.order_side == OrderSide::Sell => {
+            journal.record_update(&trade);
+            // Tiers count as sold once their order completes, before a full sell removes the position
+            if trade.status == TradeStatus::Filled {
+                position_tracker.mark_tiers_executed(&trade.token_id, &trade.take_profit_tiers);
+            }
+            apply_sell(&trade, shares, price, risk_manager, position_tracker);
+        }
+        OrderUpdate::Filled { trade, shares, price } => {
+            journal.record_update(&trade);
+            position_tracker.add_fill(&trade, shares, price);
+
+            if let Err(e) = notifier.send_fill(&trade, shares, price).await {
+                warn!("Failed to send fill notification: {}", e);
+            }
+        }
+        OrderUpdate::Cancelled { trade, .. } if trade.order_side == OrderSide::Sell => {
+            // The unsold shares stay in the position; the exit is re-evaluated next cycle.
+            // A partly filled tier sell still counts as that tier sold.
+            journal.record_update(&trade);
+            if trade.filled_size > 0.0 {
+                position_tracker.mark_tiers_executed(&trade.token_id, &trade.take_profit_tiers);
+            }
+        }
+        OrderUpdate::Cancelled { trade, unfilled_usd, nothing_filled } => {
+            journal.record_update(&trade);
+            // Basket capital is reserved under the basket ID; other legs may still fill
+            let reserved_under = trade.basket_id.as_deref().unwrap_or(&trade.condition_id);
+            risk_manager.release_unfilled(reserved_under, unfilled_usd, nothing_filled, trade.placed_on());
+        }
+        OrderUpdate::Reverted { trade, shares, price, .. } if trade.order_side == OrderSide::Sell => {
+            journal.record_update(&trade);
+            let reopened = !position_tracker.positions().iter().any(|p| p.token_id == trade.token_id);
+            position_tracker.revert_sell(&trade, shares, price);
+            let key = trade.basket_id.as_deref().unwrap_or(&trade.condition_id);
+            risk_manager.restore_exposure(key, shares * price, reopened);
+        }
+        OrderUpdate::Reverted { trade, shares, nothing_filled, .. } => {
+            journal.record_update(&trade);
+            position_tracker.revert_fill(&trade.token_id, shares);
+            // The failed shares leave the order, releasing the capital reserved for them
+            let reserved_under = trade.basket_id.as_deref().unwrap_or(&trade.condition_id);
+            risk_manager.release_unfilled(reserved_under, shares * trade.price, nothing_filled, trade.placed_on());
+        }
+    }
+}
+
+/// Take sold shares out of their position and release the cost basis from risk limits
+fn apply_sell(trade: &Trade, shares: f64, price: f64, risk_manager: &mut RiskManager, position_tracker: &mut PositionTracker) {
+    let fee = trade.fee_for(shares, price);
+    let Some(sold) = position_tracker.apply_sell(&trade.token_id, shares, price, fee) else { return };
+    if sold.closed {
+        risk_manager.record_close(&sold.condition_id, sold.cost_released);
+    } else {
+        risk_manager.reduce_exposure(&sold.condition_id, sold.cost_released);
     }
 }
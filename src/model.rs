@@ -0,0 +1,428 @@
+use anyhow::{Result, Context, bail};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::{info, warn, debug};
+
+use crate::config::Config;
+use crate::gamma::{GammaClient, GammaMarket, MarketQuery};
+use crate::orderbook::OrderBookClient;
+use crate::scanner::MarketOpportunity;
+
+/// Categories with their own indicator feature; everything else shares the baseline
+const MODEL_CATEGORIES: &[&str] = &["politics", "crypto", "sports", "geopolitics", "economics", "tech"];
+/// Days before resolution at which each resolved market is sampled
+const SAMPLE_HORIZONS_DAYS: &[i64] = &[1, 3, 7, 14, 30];
+/// Price history resolution in minutes
+const HISTORY_FIDELITY_MINS: u32 = 720;
+/// Maximum in-flight price history requests
+const HISTORY_FETCH_CONCURRENCY: usize = 8;
+/// Every Nth resolved market is held out for evaluation
+const HOLDOUT_EVERY: usize = 5;
+/// Trades per data API page, and pages read per market
+const TRADES_PAGE_SIZE: usize = 500;
+const MAX_TRADE_PAGES: usize = 10;
+/// Features before the category indicators
+const NUMERIC_FEATURES: usize = 4;
+
+const DATA_API_URL: &str = "https://data-api.polymarket.com";
+
+const TRAIN_EPOCHS: usize = 2000;
+const LEARNING_RATE: f64 = 0.1;
+const L2_PENALTY: f64 = 0.001;
+
+/// Logistic regression estimating the probability that an outcome bought at a given
+/// price resolves YES, fitted on resolved Gamma markets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbabilityModel {
+    pub trained_at: DateTime<Utc>,
+    pub samples: usize,
+    pub categories: Vec<String>,
+    means: Vec<f64>,
+    stds: Vec<f64>,
+    weights: Vec<f64>,
+    bias: f64,
+}
+
+/// Entry-time features of one outcome
+struct FeatureInputs<'a> {
+    price: f64,
+    days_to_resolution: f64,
+    /// Volume traded up to entry
+    volume_total: f64,
+    /// Volume traded in the 24h before entry
+    volume_24h: f64,
+    category: &'a str,
+    tags: &'a [String],
+}
+
+/// One resolved outcome sampled some days before its market closed
+struct Sample {
+    features: Vec<f64>,
+    price: f64,
+    won: bool,
+}
+
+/// Raw features: price log-odds, log lockup, log volume to date, log 24h volume and category
+/// indicators. Past order books aren't recorded, so 24h volume stands in for liquidity.
+fn features(inputs: &FeatureInputs, categories: &[String]) -> Vec<f64> {
+    let price = inputs.price.clamp(0.001, 0.999);
+    let category = inputs.category.to_lowercase();
+    let mut row = vec![
+        (price / (1.0 - price)).ln(),
+        inputs.days_to_resolution.max(0.0).ln_1p(),
+        inputs.volume_total.max(0.0).ln_1p(),
+        inputs.volume_24h.max(0.0).ln_1p(),
+    ];
+    row.extend(categories.iter().map(|c| {
+        if category == *c || inputs.tags.iter().any(|t| t == c) { 1.0 } else { 0.0 }
+    }));
+    row
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+impl ProbabilityModel {
+    /// Load a trained model from disk
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).context("Invalid probability model file")
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content).context("Failed to write probability model")
+    }
+
+    fn predict_raw(&self, raw: &[f64]) -> f64 {
+        let z = raw.iter().zip(&self.means).zip(&self.stds).zip(&self.weights)
+            .map(|(((x, mean), std), w)| (x - mean) / std * w)
+            .sum::<f64>() + self.bias;
+        sigmoid(z)
+    }
+
+    /// Predicted probability that the opportunity's outcome resolves YES
+    pub fn predict(&self, opp: &MarketOpportunity) -> f64 {
+        let inputs = FeatureInputs {
+            price: opp.price,
+            days_to_resolution: opp.days_to_resolution.unwrap_or(0.0),
+            volume_total: opp.volume_total,
+            volume_24h: opp.volume_24h,
+            category: &opp.category,
+            tags: &opp.tags,
+        };
+        self.predict_raw(&features(&inputs, &self.categories))
+    }
+
+    /// Set `predicted_probability` and `expected_value` on single-outcome opportunities
+    pub fn annotate(&self, opportunities: &mut [MarketOpportunity]) {
+        for opp in opportunities.iter_mut().filter(|o| !o.is_basket() && o.price > 0.0) {
+            let probability = self.predict(opp);
+            opp.predicted_probability = Some(probability);
+            // Return per dollar staked if held to resolution
+            opp.expected_value = Some(probability / opp.price - 1.0);
+        }
+    }
+}
+
+/// Keeps the configured model loaded, reloading it when the file changes
+pub struct ModelStore {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    model: Option<ProbabilityModel>,
+}
+
+impl ModelStore {
+    pub fn new() -> Self {
+        Self { path: None, modified: None, model: None }
+    }
+
+    /// The current model, reloaded if `probability_model_path` or its contents changed
+    pub fn current(&mut self, config: &Config) -> Option<&ProbabilityModel> {
+        let path = config.probability_model_path.as_deref().filter(|p| !p.is_empty()).map(PathBuf::from);
+        let modified = path.as_ref()
+            .and_then(|p| std::fs::metadata(p).ok())
+            .and_then(|m| m.modified().ok());
+
+        if path != self.path || modified != self.modified {
+            self.model = match (&path, modified) {
+                (Some(p), Some(_)) => match ProbabilityModel::load(p) {
+                    Ok(model) if model.weights.len() != NUMERIC_FEATURES + model.categories.len() => {
+                        warn!("Probability model has an outdated feature set - run train-model again");
+                        None
+                    }
+                    Ok(model) => {
+                        info!("🤖 Loaded probability model ({} samples, trained {})",
+                            model.samples, model.trained_at.format("%Y-%m-%d"));
+                        Some(model)
+                    }
+                    Err(e) => {
+                        warn!("Failed to load probability model: {}", e);
+                        None
+                    }
+                },
+                _ => None,
+            };
+            self.path = path;
+            self.modified = modified;
+        }
+
+        self.model.as_ref()
+    }
+}
+
+/// Download resolved markets, build the dataset, fit the model and write it to
+/// `probability_model_path` (the `train-model` subcommand)
+pub async fn train(config: &Config) -> Result<()> {
+    let Some(path) = config.probability_model_path.as_deref().filter(|p| !p.is_empty()) else {
+        bail!("probability_model_path is not set in config.json");
+    };
+
+    info!("🤖 Downloading up to {} resolved markets...", config.model_train_markets);
+    let gamma = GammaClient::new();
+    // Most recently ended first, so the sample isn't chosen by volume
+    let query = MarketQuery::new()
+        .closed(true)
+        .include_tags()
+        .order("endDate", false)
+        .end_date_range(Some(Utc::now() - Duration::days(config.model_lookback_days)), Some(Utc::now()));
+    let pages = config.model_train_markets.div_ceil(100).max(1);
+    let mut markets = gamma.fetch_pages(&query, pages).await?;
+    markets.truncate(config.model_train_markets);
+
+    let categories: Vec<String> = MODEL_CATEGORIES.iter().map(|c| c.to_string()).collect();
+    let books = OrderBookClient::new();
+    let data_api = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)).build()?;
+
+    let per_market: Vec<MarketSamples> = stream::iter(markets.iter())
+        .map(|market| market_samples(market, config, &categories, &books, &data_api))
+        .buffered(HISTORY_FETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let (mut train_set, mut holdout) = (Vec::new(), Vec::new());
+    let (mut truncated_markets, mut truncated_samples) = (0, 0);
+    for (i, market) in per_market.into_iter().enumerate() {
+        if market.truncated > 0 {
+            truncated_markets += 1;
+            truncated_samples += market.truncated;
+        }
+        if i % HOLDOUT_EVERY == 0 { holdout.extend(market.samples) } else { train_set.extend(market.samples) }
+    }
+    if truncated_samples > 0 {
+        warn!("⚠️ Skipped {} samples in {} markets whose trade history ran out before the sample time - busy markets are under-represented",
+            truncated_samples, truncated_markets);
+    }
+    if train_set.len() < 50 {
+        bail!("Only {} training samples - widen model_lookback_days or model_max_entry_price", train_set.len());
+    }
+
+    let win_rate = train_set.iter().filter(|s| s.won).count() as f64 / train_set.len() as f64;
+    info!("📚 Dataset: {} training / {} holdout samples ({:.1}% resolved YES)",
+        train_set.len(), holdout.len(), win_rate * 100.0);
+
+    let model = fit(&train_set, categories);
+
+    if !holdout.is_empty() {
+        let (model_loss, model_brier) = evaluate(&holdout, |s| model.predict_raw(&s.features));
+        let (market_loss, market_brier) = evaluate(&holdout, |s| s.price);
+        info!("📏 Holdout log loss {:.4} (market price {:.4}) | Brier {:.4} (market price {:.4})",
+            model_loss, market_loss, model_brier, market_brier);
+    }
+
+    model.save(Path::new(path))?;
+    info!("💾 Saved probability model to {}", path);
+    Ok(())
+}
+
+/// A fill in a market's trade history, as reported by the data API
+#[derive(Debug, Deserialize)]
+struct MarketTrade {
+    #[serde(default)]
+    timestamp: i64,
+    #[serde(default)]
+    size: f64,
+    #[serde(default)]
+    price: f64,
+}
+
+/// (timestamp, USDC notional) of a market's trades back to `since_ts`, newest first,
+/// plus the oldest timestamp the list is complete from
+async fn market_trades(client: &reqwest::Client, condition_id: &str, since_ts: i64) -> Result<(Vec<(i64, f64)>, i64)> {
+    let mut trades = Vec::new();
+    for page in 0..MAX_TRADE_PAGES {
+        let batch: Vec<MarketTrade> = client
+            .get(format!("{}/trades", DATA_API_URL))
+            .query(&[
+                ("market", condition_id.to_string()),
+                ("limit", TRADES_PAGE_SIZE.to_string()),
+                ("offset", (page * TRADES_PAGE_SIZE).to_string()),
+            ])
+            .send()
+            .await
+            .context("Failed to fetch market trades")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid trades response")?;
+        let done = batch.len() < TRADES_PAGE_SIZE;
+        trades.extend(batch.iter().map(|t| (t.timestamp, t.size * t.price)));
+        if done {
+            return Ok((trades, i64::MIN));
+        }
+        if trades.last().is_some_and(|(t, _)| *t < since_ts) {
+            break;
+        }
+    }
+    let covered_from = trades.last().map(|(t, _)| *t).unwrap_or(i64::MAX);
+    Ok((trades, covered_from))
+}
+
+/// Samples drawn from one resolved market
+#[derive(Default)]
+struct MarketSamples {
+    samples: Vec<Sample>,
+    /// In-range sample points dropped because the trade history didn't reach back far enough
+    truncated: usize,
+}
+
+/// Sample each outcome of a resolved market at fixed horizons before it closed, keeping
+/// entries priced within the longshot range. Volume features are rebuilt as of each sample
+/// time from the trade history, so nothing traded after entry leaks into them.
+async fn market_samples(
+    market: &GammaMarket,
+    config: &Config,
+    categories: &[String],
+    books: &OrderBookClient,
+    data_api: &reqwest::Client,
+) -> MarketSamples {
+    let mut result = MarketSamples::default();
+    let (Some(closed), Some(final_prices)) = (market.closed_at(), market.parsed_prices()) else { return result };
+    // Only markets with a clear winner
+    if !final_prices.iter().any(|p| *p >= 0.99) {
+        return result;
+    }
+
+    let final_volume: f64 = market.volume.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0);
+    let category = market.primary_category();
+    let tags = market.tag_names();
+    let max_horizon = SAMPLE_HORIZONS_DAYS.iter().max().copied().unwrap_or(30);
+    let start = closed - Duration::days(max_horizon + 1);
+
+    let (trades, covered_from) = match market_trades(data_api, &market.condition_id, start.timestamp()).await {
+        Ok(trades) => trades,
+        Err(e) => {
+            debug!("No trade history for {}: {}", market.question, e);
+            return result;
+        }
+    };
+    let traded_between = |from: i64, to: i64| -> f64 {
+        trades.iter().filter(|(t, _)| *t > from && *t <= to).map(|(_, notional)| notional).sum()
+    };
+
+    for (index, token_id) in market.token_ids().iter().enumerate() {
+        let Some(&final_price) = final_prices.get(index) else { continue };
+        let history = match books.price_history(token_id, start.timestamp(), closed.timestamp(), HISTORY_FIDELITY_MINS).await {
+            Ok(history) => history,
+            Err(e) => {
+                debug!("No price history for {}: {}", market.question, e);
+                continue;
+            }
+        };
+
+        for days in SAMPLE_HORIZONS_DAYS {
+            let at = (closed - Duration::days(*days)).timestamp();
+            let day_before = at - 86_400;
+            let Some(&(_, price)) = history.iter().rev().find(|(t, _)| *t <= at) else { continue };
+            if price <= 0.001 || price > config.model_max_entry_price {
+                continue;
+            }
+            // The trade history must reach back over the day before the sample
+            if covered_from > day_before {
+                result.truncated += 1;
+                continue;
+            }
+
+            let inputs = FeatureInputs {
+                price,
+                days_to_resolution: *days as f64,
+                volume_total: (final_volume - traded_between(at, i64::MAX)).max(0.0),
+                volume_24h: traded_between(day_before, at),
+                category: &category,
+                tags: &tags,
+            };
+            result.samples.push(Sample { features: features(&inputs, categories), price, won: final_price >= 0.99 });
+        }
+    }
+    if result.truncated > 0 {
+        info!("✂️ {}: skipped {} of {} samples - trade history covers only {:.1} days before close",
+            market.question, result.truncated, result.truncated + result.samples.len(),
+            (closed.timestamp() - covered_from) as f64 / 86_400.0);
+    }
+    result
+}
+
+/// Fit L2-regularised logistic regression by full-batch gradient descent on standardised features
+fn fit(samples: &[Sample], categories: Vec<String>) -> ProbabilityModel {
+    let n = samples.len() as f64;
+    let dims = samples[0].features.len();
+
+    let means: Vec<f64> = (0..dims)
+        .map(|j| samples.iter().map(|s| s.features[j]).sum::<f64>() / n)
+        .collect();
+    let stds: Vec<f64> = (0..dims)
+        .map(|j| {
+            let var = samples.iter().map(|s| (s.features[j] - means[j]).powi(2)).sum::<f64>() / n;
+            var.sqrt().max(1e-6)
+        })
+        .collect();
+    let xs: Vec<Vec<f64>> = samples.iter()
+        .map(|s| s.features.iter().enumerate().map(|(j, x)| (x - means[j]) / stds[j]).collect())
+        .collect();
+
+    let mut weights = vec![0.0; dims];
+    let mut bias = 0.0;
+    for _ in 0..TRAIN_EPOCHS {
+        let mut grad_w = vec![0.0; dims];
+        let mut grad_b = 0.0;
+        for (x, sample) in xs.iter().zip(samples) {
+            let z = x.iter().zip(&weights).map(|(x, w)| x * w).sum::<f64>() + bias;
+            let error = sigmoid(z) - if sample.won { 1.0 } else { 0.0 };
+            for (g, x) in grad_w.iter_mut().zip(x) {
+                *g += error * x;
+            }
+            grad_b += error;
+        }
+        for (w, g) in weights.iter_mut().zip(&grad_w) {
+            *w -= LEARNING_RATE * (g / n + L2_PENALTY * *w);
+        }
+        bias -= LEARNING_RATE * grad_b / n;
+    }
+
+    ProbabilityModel {
+        trained_at: Utc::now(),
+        samples: samples.len(),
+        categories,
+        means,
+        stds,
+        weights,
+        bias,
+    }
+}
+
+/// (log loss, Brier score) of a probability forecast over the samples
+fn evaluate(samples: &[Sample], forecast: impl Fn(&Sample) -> f64) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let (mut loss, mut brier) = (0.0, 0.0);
+    for sample in samples {
+        let p = forecast(sample).clamp(1e-6, 1.0 - 1e-6);
+        let y = if sample.won { 1.0 } else { 0.0 };
+        loss -= y * p.ln() + (1.0 - y) * (1.0 - p).ln();
+        brier += (p - y).powi(2);
+    }
+    (loss / n, brier / n)
+}
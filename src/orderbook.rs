@@ -0,0 +1,289 @@
+use anyhow::{Result, Context};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+const CLOB_URL: &str = "https://clob.polymarket.com";
+/// Token IDs per batch order book request
+const BOOKS_BATCH_SIZE: usize = 50;
+
+/// One price level of a CLOB order book
+#[derive(Debug, Clone, Copy)]
+pub struct BookLevel {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// Order book for one outcome token; bids best (highest) first, asks best (lowest) first
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+    /// Minimum price increment of the market
+    pub tick_size: f64,
+}
+
+impl OrderBook {
+    /// Best ask minus best bid; a missing bid counts as zero
+    pub fn spread(&self) -> Option<f64> {
+        let ask = self.asks.first()?.price;
+        let bid = self.bids.first().map_or(0.0, |l| l.price);
+        Some(ask - bid)
+    }
+
+    /// Shares offered at or below `price` plus `ticks` ticks
+    pub fn ask_depth_within(&self, price: f64, ticks: u32) -> f64 {
+        let limit = price + ticks as f64 * self.tick_size + 1e-9;
+        self.asks.iter()
+            .take_while(|l| l.price <= limit)
+            .map(|l| l.size)
+            .sum()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawLevel {
+    price: String,
+    size: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHistoryPoint {
+    t: i64,
+    p: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHistory {
+    #[serde(default)]
+    history: Vec<RawHistoryPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawBook {
+    #[serde(default)]
+    asset_id: String,
+    #[serde(default)]
+    bids: Vec<RawLevel>,
+    #[serde(default)]
+    asks: Vec<RawLevel>,
+    #[serde(default)]
+    tick_size: Option<String>,
+}
+
+impl RawBook {
+    fn into_book(self) -> OrderBook {
+        let parse = |levels: Vec<RawLevel>| -> Vec<BookLevel> {
+            levels.into_iter()
+                .filter_map(|l| Some(BookLevel { price: l.price.parse().ok()?, size: l.size.parse().ok()? }))
+                .filter(|l| l.size > 0.0)
+                .collect()
+        };
+
+        let tick_size = self.tick_size.and_then(|t| t.parse().ok()).unwrap_or(0.01);
+        let mut book = OrderBook { bids: parse(self.bids), asks: parse(self.asks), tick_size };
+        // The CLOB returns levels worst-first; normalise to best-first
+        book.bids.sort_by(|x, y| y.price.partial_cmp(&x.price).unwrap_or(std::cmp::Ordering::Equal));
+        book.asks.sort_by(|x, y| x.price.partial_cmp(&y.price).unwrap_or(std::cmp::Ordering::Equal));
+        book
+    }
+}
+
+/// Depth both legs of a pair can be bought at together
+#[derive(Debug, Clone, Copy)]
+pub struct PairFill {
+    /// Shares of each leg
+    pub shares: f64,
+    /// Total cost of both legs
+    pub cost: f64,
+    /// Highest ask reached on each leg (the limit price that sweeps the depth)
+    pub worst_prices: (f64, f64),
+}
+
+/// Walk two ask ladders together, buying equal shares of both while the combined
+/// price of the next share stays at or below `max_pair_price`
+pub fn match_pair_depth(a: &[BookLevel], b: &[BookLevel], max_pair_price: f64) -> Option<PairFill> {
+    let (mut i, mut j) = (0, 0);
+    let (mut left_a, mut left_b) = (a.first()?.size, b.first()?.size);
+    let mut fill = PairFill { shares: 0.0, cost: 0.0, worst_prices: (0.0, 0.0) };
+
+    while i < a.len() && j < b.len() {
+        let (pa, pb) = (a[i].price, b[j].price);
+        if pa + pb > max_pair_price {
+            break;
+        }
+
+        let shares = left_a.min(left_b);
+        fill.shares += shares;
+        fill.cost += shares * (pa + pb);
+        fill.worst_prices = (pa, pb);
+
+        left_a -= shares;
+        left_b -= shares;
+        if left_a <= 1e-9 {
+            i += 1;
+            left_a = a.get(i).map_or(0.0, |l| l.size);
+        }
+        if left_b <= 1e-9 {
+            j += 1;
+            left_b = b.get(j).map_or(0.0, |l| l.size);
+        }
+    }
+
+    (fill.shares > 0.0).then_some(fill)
+}
+
+/// Depth every leg of a basket can be bought at together
+#[derive(Debug, Clone)]
+pub struct BasketFill {
+    /// Shares of each leg
+    pub shares: f64,
+    /// Total cost of all legs
+    pub cost: f64,
+    /// Highest ask reached on each leg, in leg order
+    pub worst_prices: Vec<f64>,
+}
+
+/// Walk the ask ladders of every leg together, buying equal shares of each while the
+/// combined price of the next basket stays at or below `max_basket_price`
+pub fn match_basket_depth(ladders: &[&[BookLevel]], max_basket_price: f64) -> Option<BasketFill> {
+    let mut levels = vec![0; ladders.len()];
+    let mut left: Vec<f64> = ladders.iter().map(|l| l.first().map(|level| level.size)).collect::<Option<_>>()?;
+    let mut fill = BasketFill { shares: 0.0, cost: 0.0, worst_prices: vec![0.0; ladders.len()] };
+
+    let level_prices = |levels: &[usize]| -> Option<Vec<f64>> {
+        ladders.iter().zip(levels).map(|(l, &i)| l.get(i).map(|level| level.price)).collect()
+    };
+    while let Some(prices) = level_prices(&levels) {
+        let basket_price: f64 = prices.iter().sum();
+        if basket_price > max_basket_price {
+            break;
+        }
+
+        let shares = left.iter().copied().fold(f64::INFINITY, f64::min);
+        fill.shares += shares;
+        fill.cost += shares * basket_price;
+        fill.worst_prices = prices;
+
+        for (leg, ladder) in ladders.iter().enumerate() {
+            left[leg] -= shares;
+            if left[leg] <= 1e-9 {
+                levels[leg] += 1;
+                left[leg] = ladder.get(levels[leg]).map_or(0.0, |l| l.size);
+            }
+        }
+    }
+
+    (fill.shares > 0.0).then_some(fill)
+}
+
+/// Public (unauthenticated) CLOB order book and price history reader
+pub struct OrderBookClient {
+    client: reqwest::Client,
+}
+
+impl OrderBookClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// Fetch the current order book for an outcome token
+    pub async fn fetch(&self, token_id: &str) -> Result<OrderBook> {
+        let raw: RawBook = self.client
+            .get(format!("{}/book", CLOB_URL))
+            .query(&[("token_id", token_id)])
+            .send()
+            .await
+            .context("Failed to fetch order book")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid order book response")?;
+
+        Ok(raw.into_book())
+    }
+
+    /// Fetch the order books of many outcome tokens in batched requests, keyed by token ID
+    pub async fn fetch_many(&self, token_ids: &[String]) -> Result<HashMap<String, OrderBook>> {
+        let mut books = HashMap::new();
+        for chunk in token_ids.chunks(BOOKS_BATCH_SIZE) {
+            let body: Vec<_> = chunk.iter().map(|id| json!({ "token_id": id })).collect();
+            let raw: Vec<RawBook> = self.client
+                .post(format!("{}/books", CLOB_URL))
+                .json(&body)
+                .send()
+                .await
+                .context("Failed to fetch order books")?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Invalid order books response")?;
+            books.extend(raw.into_iter().map(|book| (book.asset_id.clone(), book.into_book())));
+        }
+        Ok(books)
+    }
+
+    /// Fetch (unix seconds, price) points for an outcome token between two timestamps,
+    /// one point per `fidelity_mins` minutes
+    pub async fn price_history(&self, token_id: &str, start_ts: i64, end_ts: i64, fidelity_mins: u32) -> Result<Vec<(i64, f64)>> {
+        let raw: RawHistory = self.client
+            .get(format!("{}/prices-history", CLOB_URL))
+            .query(&[
+                ("market", token_id.to_string()),
+                ("startTs", start_ts.to_string()),
+                ("endTs", end_ts.to_string()),
+                ("fidelity", fidelity_mins.to_string()),
+            ])
+            .send()
+            .await
+            .context("Failed to fetch price history")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid price history response")?;
+
+        Ok(raw.history.into_iter().map(|point| (point.t, point.p)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(levels: &[(f64, f64)]) -> Vec<BookLevel> {
+        levels.iter().map(|&(price, size)| BookLevel { price, size }).collect()
+    }
+
+    #[test]
+    fn basket_depth_stops_at_the_price_cap() {
+        let a = levels(&[(0.30, 10.0), (0.32, 20.0), (0.40, 100.0)]);
+        let b = levels(&[(0.25, 15.0), (0.30, 100.0)]);
+        let c = levels(&[(0.35, 100.0)]);
+        let fill = match_basket_depth(&[&a, &b, &c], 0.97).unwrap();
+
+        // 10 @ 0.90, 5 @ 0.92, 15 @ 0.97; then leg a reaches 0.40 and the basket costs 1.05
+        assert!((fill.shares - 30.0).abs() < 1e-9);
+        assert!((fill.cost - (10.0 * 0.90 + 5.0 * 0.92 + 15.0 * 0.97)).abs() < 1e-9);
+        assert_eq!(fill.worst_prices, vec![0.32, 0.30, 0.35]);
+    }
+
+    #[test]
+    fn basket_depth_needs_every_leg() {
+        let a = levels(&[(0.30, 10.0)]);
+        assert!(match_basket_depth(&[&a, &[]], 1.0).is_none());
+        assert!(match_basket_depth(&[&a, &a, &a, &a], 1.0).is_none());
+    }
+
+    #[test]
+    fn basket_depth_ends_with_the_shortest_ladder() {
+        let a = levels(&[(0.40, 5.0)]);
+        let b = levels(&[(0.40, 50.0)]);
+        let fill = match_basket_depth(&[&a, &b], 1.0).unwrap();
+        assert!((fill.shares - 5.0).abs() < 1e-9);
+    }
+}
@@ -1,128 +1,449 @@
-use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use tracing::{info, debug};
+use std::collections::HashMap;
+use tracing::info;
 
-use crate::config::Config;
+use crate::config::{Config, TakeProfitTier};
 use crate::executor::Trade;
 
 /// Tracks open positions and monitors for exit signals
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Position {
     pub condition_id: String,
     pub token_id: String,
     pub question: String,
+    /// Outcome label held (e.g. "Yes", "Lakers")
     pub side: String,
+    #[serde(default)]
+    pub outcome_index: usize,
+    /// Average price of the open lots
     pub entry_price: f64,
     pub current_price: f64,
     pub shares: f64,
+    /// Cost of the open lots, excluding fees
     pub cost_usd: f64,
     pub current_value: f64,
+    /// Unrealized P/L of the open lots, net of their buy fees
     pub pnl: f64,
     pub pnl_pct: f64,
     pub url: String,
     pub entered_at: String,
-    pub partial_sold: bool,
+    /// Open buy lots, oldest first; sells consume them first-in first-out
+    #[serde(default)]
+    pub lots: Vec<Lot>,
+    /// P/L locked in by sells out of this position, net of fees
+    #[serde(default)]
+    pub realized_pnl: f64,
+    /// Fees paid on every buy and sell of this position
+    #[serde(default)]
+    pub fees_usd: f64,
+    /// Shares bought into the position, before any sells
+    #[serde(default)]
+    pub initial_shares: f64,
+    /// Take-profit tiers already sold, as configured when they filled
+    #[serde(default)]
+    pub executed_tiers: Vec<TakeProfitTier>,
+    /// Opportunity condition ID of the hedged basket this position is a leg of
+    #[serde(default)]
+    pub basket_id: Option<String>,
+    /// Guaranteed payout per share of the full basket
+    #[serde(default)]
+    pub basket_payout: Option<f64>,
+    /// Highest price seen since entry
+    #[serde(default)]
+    pub high_water_price: f64,
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// Scheduled resolution of the market
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+/// Shares bought in one fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lot {
+    pub shares: f64,
+    pub price: f64,
+    /// Buy fee of the shares still in the lot
+    pub fee_usd: f64,
+    pub acquired_at: String,
+}
+
+impl Position {
+    /// Recompute size, cost and P/L from the open lots and the current price
+    fn refresh(&mut self) {
+        self.shares = self.lots.iter().map(|l| l.shares).sum();
+        self.cost_usd = self.lots.iter().map(|l| l.shares * l.price).sum();
+        if self.shares > 0.0 {
+            self.entry_price = self.cost_usd / self.shares;
+        }
+        let open_fees: f64 = self.lots.iter().map(|l| l.fee_usd).sum();
+        self.current_value = self.shares * self.current_price;
+        self.pnl = self.current_value - self.cost_usd - open_fees;
+        self.pnl_pct = if self.cost_usd > 0.0 { (self.pnl / self.cost_usd) * 100.0 } else { 0.0 };
+    }
+
+    /// Whether a tier at this multiple has already been sold
+    fn tier_executed(&self, tier: &TakeProfitTier) -> bool {
+        self.executed_tiers.iter().any(|t| (t.multiple - tier.multiple).abs() < 1e-9)
+    }
+
+    /// Remove `shares` from the oldest lots, returning their cost and buy fees
+    fn consume_lots(&mut self, mut shares: f64) -> (f64, f64) {
+        let (mut cost, mut fees) = (0.0, 0.0);
+        for lot in &mut self.lots {
+            if shares <= 1e-9 {
+                break;
+            }
+            let taken = shares.min(lot.shares);
+            let fee = lot.fee_usd * taken / lot.shares;
+            cost += taken * lot.price;
+            fees += fee;
+            lot.fee_usd -= fee;
+            lot.shares -= taken;
+            shares -= taken;
+        }
+        self.lots.retain(|l| l.shares > 1e-9);
+        self.refresh();
+        (cost, fees)
+    }
+}
+
+/// Result of selling shares out of a position
+#[derive(Debug, Clone)]
+pub struct SellResult {
+    pub condition_id: String,
+    /// Cost basis of the shares sold
+    pub cost_released: f64,
+    /// The position has no shares left and was removed
+    pub closed: bool,
+}
+
+/// Legs of one hedged basket viewed as a single position
+#[derive(Debug, Clone)]
+pub struct BasketPosition {
+    pub basket_id: String,
+    pub legs: usize,
+    /// Complete basket shares held (the smallest leg)
+    pub shares: f64,
+    pub cost_usd: f64,
+    /// Payout of the complete shares at resolution
+    pub payout_usd: f64,
+    pub locked_profit: f64,
+}
+
+/// A position closed out by its market resolving
+#[derive(Debug, Clone)]
+pub struct Settlement {
+    pub position: Position,
+    /// Price each share settled at (1.0 won, 0.0 lost, in between for a split market)
+    pub payout_price: f64,
+    pub proceeds: f64,
+    /// Realized P/L of the settled shares, net of their buy fees
+    pub pnl: f64,
+    /// Totals of the whole basket, once this was its last leg to settle
+    pub basket: Option<BasketSettlement>,
+}
+
+impl Settlement {
+    pub fn outcome_label(&self) -> &'static str {
+        settlement_label(self.payout_price)
+    }
+}
+
+fn settlement_label(payout_price: f64) -> &'static str {
+    if payout_price >= 0.99 {
+        "Won"
+    } else if payout_price <= 0.0 {
+        "Lost"
+    } else {
+        "Split"
+    }
+}
+
+/// Realized result of a hedged basket whose legs have all settled
+#[derive(Debug, Clone, Default)]
+pub struct BasketSettlement {
+    pub legs: usize,
+    pub cost_usd: f64,
+    pub proceeds: f64,
 }
 
 pub struct PositionTracker {
     positions: Vec<Position>,
+    /// Running totals of baskets with some legs settled
+    settling_baskets: HashMap<String, BasketSettlement>,
+    /// P/L realized by sells and settlements across all positions, net of fees
+    realized_pnl: f64,
+    /// Fees paid across all positions
+    fees_usd: f64,
 }
 
 impl PositionTracker {
     pub fn new() -> Self {
         Self {
             positions: Vec::new(),
+            settling_baskets: HashMap::new(),
+            realized_pnl: 0.0,
+            fees_usd: 0.0,
         }
     }
 
     /// Add a new position from a filled trade
     pub fn add_from_trade(&mut self, trade: &Trade) {
-        // Check if we already have this position
-        if let Some(pos) = self.positions.iter_mut().find(|p| p.condition_id == trade.condition_id) {
-            // Average in
-            let total_shares = pos.shares + trade.size;
-            let total_cost = pos.cost_usd + trade.cost_usd;
-            pos.entry_price = total_cost / total_shares;
-            pos.shares = total_shares;
-            pos.cost_usd = total_cost;
-            info!("📊 Averaged into position: {} (now {:.0} shares @ ${:.4})", pos.question, pos.shares, pos.entry_price);
+        self.add_fill(trade, trade.size, trade.price);
+    }
+
+    /// Add `shares` filled at `price` for a trade's market as a new lot, opening the position if needed
+    pub fn add_fill(&mut self, trade: &Trade, shares: f64, price: f64) {
+        let fee = trade.fee_for(shares, price);
+        self.fees_usd += fee;
+        let lot = Lot { shares, price, fee_usd: fee, acquired_at: Utc::now().to_rfc3339() };
+
+        // Check if we already hold this outcome
+        if let Some(pos) = self.positions.iter_mut().find(|p| p.token_id == trade.token_id) {
+            pos.lots.push(lot);
+            pos.initial_shares += shares;
+            pos.fees_usd += fee;
+            pos.refresh();
+            info!("📊 Added lot to position: {} (now {:.0} shares @ ${:.4} avg)", pos.question, pos.shares, pos.entry_price);
         } else {
-            let position = Position {
+            let mut position = Position {
                 condition_id: trade.condition_id.clone(),
                 token_id: trade.token_id.clone(),
                 question: trade.question.clone(),
                 side: trade.side.clone(),
-                entry_price: trade.price,
-                current_price: trade.price,
-                shares: trade.size,
-                cost_usd: trade.cost_usd,
-                current_value: trade.cost_usd,
+                outcome_index: trade.outcome_index,
+                entry_price: price,
+                current_price: price,
+                shares: 0.0,
+                cost_usd: 0.0,
+                current_value: 0.0,
                 pnl: 0.0,
                 pnl_pct: 0.0,
                 url: trade.url.clone(),
                 entered_at: trade.placed_at.clone(),
-                partial_sold: false,
+                lots: vec![lot],
+                realized_pnl: 0.0,
+                fees_usd: fee,
+                initial_shares: shares,
+                executed_tiers: Vec::new(),
+                basket_id: trade.basket_id.clone(),
+                basket_payout: trade.basket_payout,
+                high_water_price: price,
+                neg_risk: trade.neg_risk,
+                end_date: trade.end_date.clone(),
             };
-            info!("📊 New position: {} {} {:.0} shares @ ${:.4}", trade.side, trade.question, trade.size, trade.price);
+            position.refresh();
+            info!("📊 New position: {} {} {:.0} shares @ ${:.4}", trade.side, trade.question, shares, price);
             self.positions.push(position);
         }
     }
 
-    /// Update prices for all positions and check for exit signals
-    pub fn update_prices(&mut self, price_updates: &[(String, f64)]) -> Vec<ExitSignal> {
-        let mut signals = Vec::new();
+    /// Track a position opened outside the bot, built from its lots
+    pub fn import(&mut self, mut position: Position) {
+        position.refresh();
+        info!("📥 Imported position: {} {} {:.0} shares @ ${:.4}", position.side, position.question, position.shares, position.entry_price);
+        self.positions.push(position);
+    }
 
-        for (condition_id, new_price) in price_updates {
-            if let Some(pos) = self.positions.iter_mut().find(|p| p.condition_id == *condition_id) {
+    /// Update prices for all positions from (token_id, price) pairs of the held outcome
+    pub fn update_prices(&mut self, price_updates: &[(String, f64)]) {
+        for (token_id, new_price) in price_updates {
+            if let Some(pos) = self.positions.iter_mut().find(|p| p.token_id == *token_id) {
                 pos.current_price = *new_price;
-                pos.current_value = pos.shares * new_price;
-                pos.pnl = pos.current_value - pos.cost_usd;
-                pos.pnl_pct = if pos.cost_usd > 0.0 { (pos.pnl / pos.cost_usd) * 100.0 } else { 0.0 };
+                pos.high_water_price = pos.high_water_price.max(*new_price);
+                pos.refresh();
             }
         }
+    }
 
-        signals
+    /// Remove `shares` sold at `price` (paying `fee`) from a position's oldest lots, realizing their P/L
+    pub fn apply_sell(&mut self, token_id: &str, shares: f64, price: f64, fee: f64) -> Option<SellResult> {
+        let idx = self.positions.iter().position(|p| p.token_id == token_id)?;
+        let pos = &mut self.positions[idx];
+        let shares = shares.min(pos.shares);
+        let (cost_released, buy_fees) = pos.consume_lots(shares);
+        let proceeds = shares * price;
+        let realized_pnl = proceeds - cost_released - buy_fees - fee;
+        pos.realized_pnl += realized_pnl;
+        pos.fees_usd += fee;
+        self.realized_pnl += realized_pnl;
+        self.fees_usd += fee;
+
+        let result = SellResult {
+            condition_id: pos.condition_id.clone(),
+            cost_released,
+            closed: pos.shares <= 1e-6,
+        };
+        info!("📤 Sold {:.0} {} {} @ ${:.4} (realized P/L ${:+.2})",
+            shares, pos.side, pos.question, price, realized_pnl);
+
+        if result.closed {
+            self.positions.remove(idx);
+        }
+        Some(result)
     }
 
-    /// Check all positions for exit signals based on config
-    pub fn check_exits(&self, config: &Config) -> Vec<ExitSignal> {
-        let mut signals = Vec::new();
+    /// Take `shares` of a buy whose match failed on chain back out of the newest lots,
+    /// undoing their fees without realizing any P/L
+    pub fn revert_fill(&mut self, token_id: &str, shares: f64) -> Option<SellResult> {
+        let idx = self.positions.iter().position(|p| p.token_id == token_id)?;
+        let pos = &mut self.positions[idx];
+        let mut remaining = shares.min(pos.shares);
+        let mut cost_released = 0.0;
+        for lot in pos.lots.iter_mut().rev() {
+            if remaining <= 1e-9 {
+                break;
+            }
+            let taken = remaining.min(lot.shares);
+            let fee = lot.fee_usd * taken / lot.shares;
+            cost_released += taken * lot.price;
+            lot.fee_usd -= fee;
+            lot.shares -= taken;
+            pos.fees_usd -= fee;
+            self.fees_usd -= fee;
+            remaining -= taken;
+        }
+        pos.lots.retain(|l| l.shares > 1e-9);
+        pos.initial_shares = (pos.initial_shares - shares).max(0.0);
+        pos.refresh();
 
-        for pos in &self.positions {
-            let price_multiple = pos.current_price / pos.entry_price;
+        let result = SellResult {
+            condition_id: pos.condition_id.clone(),
+            cost_released,
+            closed: pos.shares <= 1e-6,
+        };
+        info!("↩️ Reverted {:.0} {} {} after a failed match", shares, pos.side, pos.question);
+        if result.closed {
+            self.positions.remove(idx);
+        }
+        Some(result)
+    }
 
-            // Full exit: price hit auto_sell_multiplier
-            if price_multiple >= config.auto_sell_multiplier {
-                signals.push(ExitSignal {
-                    condition_id: pos.condition_id.clone(),
-                    token_id: pos.token_id.clone(),
-                    question: pos.question.clone(),
-                    side: pos.side.clone(),
-                    signal_type: ExitType::FullExit,
-                    shares_to_sell: pos.shares,
-                    current_price: pos.current_price,
-                    entry_price: pos.entry_price,
-                    pnl_pct: pos.pnl_pct,
-                });
+    /// Put back `shares` of a sell whose match failed on chain, as a lot at the sale price.
+    /// The sale's realized P/L stands, offset by the new lot, and its fee is refunded.
+    pub fn revert_sell(&mut self, trade: &Trade, shares: f64, price: f64) {
+        let fee = trade.fee_for(shares, price);
+        self.realized_pnl += fee;
+        self.fees_usd -= fee;
+        let reopened = !self.positions.iter().any(|p| p.token_id == trade.token_id);
+        let restored = Trade { fee_rate_bps: 0.0, ..trade.clone() };
+        self.add_fill(&restored, shares, price);
+        if let Some(pos) = self.positions.iter_mut().find(|p| p.token_id == trade.token_id) {
+            if !reopened {
+                pos.realized_pnl += fee;
+                pos.fees_usd -= fee;
+                pos.initial_shares -= shares;
+            }
+        }
+    }
+
+    /// Close a position whose market resolved, paying out `payout_price` per share
+    pub fn settle(&mut self, token_id: &str, payout_price: f64) -> Option<Settlement> {
+        let idx = self.positions.iter().position(|p| p.token_id == token_id)?;
+        let position = self.positions.remove(idx);
+        let proceeds = position.shares * payout_price;
+        let open_fees: f64 = position.lots.iter().map(|l| l.fee_usd).sum();
+        let pnl = proceeds - position.cost_usd - open_fees;
+        self.realized_pnl += pnl;
+        info!("🏁 Resolved {}: {} {} settled @ ${:.2} ({:.0} shares, P/L ${:+.2})",
+            settlement_label(payout_price).to_uppercase(), position.side, position.question,
+            payout_price, position.shares, pnl);
+
+        let basket = position.basket_id.as_ref().and_then(|basket_id| {
+            let totals = self.settling_baskets.entry(basket_id.clone()).or_default();
+            totals.legs += 1;
+            totals.cost_usd += position.cost_usd;
+            totals.proceeds += proceeds;
+
+            if self.positions.iter().any(|p| p.basket_id.as_ref() == Some(basket_id)) {
+                return None;
             }
-            // Partial exit: price hit partial_sell_multiplier (sell half)
-            else if !pos.partial_sold && price_multiple >= config.partial_sell_multiplier {
-                signals.push(ExitSignal {
+            let totals = self.settling_baskets.remove(basket_id)?;
+            info!("🧺 Basket settled: {} legs, cost ${:.2}, payout ${:.2} (P/L ${:+.2})",
+                totals.legs, totals.cost_usd, totals.proceeds, totals.proceeds - totals.cost_usd);
+            Some(totals)
+        });
+
+        Some(Settlement { position, payout_price, proceeds, pnl, basket })
+    }
+
+    /// Record that the take-profit tiers of a position have been sold
+    pub fn mark_tiers_executed(&mut self, token_id: &str, tiers: &[TakeProfitTier]) {
+        if let Some(pos) = self.positions.iter_mut().find(|p| p.token_id == token_id) {
+            for tier in tiers {
+                if !pos.tier_executed(tier) {
+                    pos.executed_tiers.push(tier.clone());
+                }
+            }
+        }
+    }
+
+    /// Check all positions for exit signals based on config.
+    /// Protective exits (pre-resolution, holding period, stop-loss, trailing stop) take
+    /// precedence over take-profit.
+    pub fn check_exits(&self, config: &Config) -> Vec<ExitSignal> {
+        let now = Utc::now();
+        let ladder = config.take_profit_tiers();
+        let hours_since = |ts: &str| DateTime::parse_from_rfc3339(ts).ok()
+            .map(|t| now.signed_duration_since(t.with_timezone(&Utc)).num_minutes() as f64 / 60.0);
+
+        // Basket legs are hedged against each other and held to resolution
+        self.positions.iter()
+            .filter(|p| p.basket_id.is_none() && p.entry_price > 0.0 && p.current_price > 0.0)
+            .filter_map(|pos| {
+                let price_multiple = pos.current_price / pos.entry_price;
+                let hours_to_end = pos.end_date.as_deref().and_then(hours_since).map(|h| -h);
+                let held_hours = hours_since(&pos.entered_at);
+
+                let signal_type = if config.exit_before_end_hours.zip(hours_to_end).is_some_and(|(limit, left)| left <= limit) {
+                    ExitType::PreResolution
+                } else if config.max_holding_hours.zip(held_hours).is_some_and(|(limit, held)| held >= limit) {
+                    ExitType::MaxHoldingPeriod
+                } else if config.stop_loss_fraction.is_some_and(|f| price_multiple <= f) {
+                    ExitType::StopLoss
+                } else if config.trailing_stop_pct.is_some_and(|pct| {
+                    pos.high_water_price > pos.entry_price && pos.current_price <= pos.high_water_price * (1.0 - pct)
+                }) {
+                    ExitType::TrailingStop
+                } else {
+                    // Every reached tier not yet sold, so a jump past several tiers sells them together
+                    let tiers: Vec<TakeProfitTier> = ladder.iter()
+                        .filter(|t| price_multiple >= t.multiple && !pos.tier_executed(t))
+                        .cloned()
+                        .collect();
+                    let multiple = tiers.last()?.multiple;
+                    ExitType::TakeProfit { multiple, tiers }
+                };
+
+                let shares_to_sell = match &signal_type {
+                    ExitType::TakeProfit { tiers, .. } => {
+                        let fraction_of = |tiers: &[TakeProfitTier]| tiers.iter().map(|t| t.sell_fraction).sum::<f64>();
+                        if fraction_of(&pos.executed_tiers) + fraction_of(tiers) >= 1.0 - 1e-9 {
+                            pos.shares
+                        } else {
+                            (pos.initial_shares * fraction_of(tiers)).min(pos.shares)
+                        }
+                    }
+                    _ => pos.shares,
+                };
+                Some(ExitSignal {
                     condition_id: pos.condition_id.clone(),
                     token_id: pos.token_id.clone(),
                     question: pos.question.clone(),
                     side: pos.side.clone(),
-                    signal_type: ExitType::PartialExit,
-                    shares_to_sell: pos.shares / 2.0,
+                    outcome_index: pos.outcome_index,
+                    url: pos.url.clone(),
+                    neg_risk: pos.neg_risk,
+                    signal_type,
+                    shares_to_sell,
                     current_price: pos.current_price,
                     entry_price: pos.entry_price,
                     pnl_pct: pos.pnl_pct,
-                });
-            }
-        }
-
-        signals
+                })
+            })
+            .collect()
     }
 
     /// Get all open positions
@@ -130,9 +451,44 @@ impl PositionTracker {
         &self.positions
     }
 
-    /// Get position condition IDs
+    /// Group basket legs into hedged positions with their locked-in profit.
+    /// Cost includes any unmatched excess on a partially filled leg.
+    pub fn baskets(&self) -> Vec<BasketPosition> {
+        let mut baskets: Vec<BasketPosition> = Vec::new();
+        for pos in &self.positions {
+            let Some(basket_id) = &pos.basket_id else { continue };
+            let idx = match baskets.iter().position(|b| b.basket_id == *basket_id) {
+                Some(idx) => idx,
+                None => {
+                    baskets.push(BasketPosition {
+                        basket_id: basket_id.clone(),
+                        legs: 0,
+                        shares: f64::INFINITY,
+                        cost_usd: 0.0,
+                        payout_usd: 0.0,
+                        locked_profit: 0.0,
+                    });
+                    baskets.len() - 1
+                }
+            };
+            let basket = &mut baskets[idx];
+            basket.legs += 1;
+            basket.shares = basket.shares.min(pos.shares);
+            basket.cost_usd += pos.cost_usd;
+            basket.payout_usd = basket.shares * pos.basket_payout.unwrap_or(0.0);
+        }
+
+        for basket in &mut baskets {
+            basket.locked_profit = basket.payout_usd - basket.cost_usd;
+        }
+        baskets
+    }
+
+    /// Get position condition IDs, including the IDs of held baskets
     pub fn position_ids(&self) -> Vec<String> {
-        self.positions.iter().map(|p| p.condition_id.clone()).collect()
+        self.positions.iter()
+            .flat_map(|p| std::iter::once(p.condition_id.clone()).chain(p.basket_id.clone()))
+            .collect()
     }
 
     /// Total portfolio value
@@ -145,28 +501,48 @@ impl PositionTracker {
         self.positions.iter().map(|p| p.cost_usd).sum()
     }
 
-    /// Total P/L
+    /// P/L realized by sells and settlements, net of fees
+    pub fn realized_pnl(&self) -> f64 {
+        self.realized_pnl
+    }
+
+    /// P/L of open lots at current prices, net of their buy fees
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.positions.iter().map(|p| p.pnl).sum()
+    }
+
+    /// Realized plus unrealized P/L
     pub fn total_pnl(&self) -> f64 {
-        self.total_value() - self.total_cost()
+        self.realized_pnl() + self.unrealized_pnl()
     }
 
     /// Portfolio summary string
     pub fn summary(&self) -> String {
-        let total_pnl = self.total_pnl();
-        let pnl_pct = if self.total_cost() > 0.0 {
-            (total_pnl / self.total_cost()) * 100.0
+        let unrealized = self.unrealized_pnl();
+        let unrealized_pct = if self.total_cost() > 0.0 {
+            (unrealized / self.total_cost()) * 100.0
         } else {
             0.0
         };
 
-        format!(
-            "💼 Portfolio: {} positions | Cost: ${:.2} | Value: ${:.2} | P/L: ${:.2} ({:+.1}%)",
+        let mut summary = format!(
+            "💼 Portfolio: {} positions | Cost: ${:.2} | Value: ${:.2} | Realized: ${:.2} | Unrealized: ${:.2} ({:+.1}%) | Total P/L: ${:.2} | Fees: ${:.2}",
             self.positions.len(),
             self.total_cost(),
             self.total_value(),
-            total_pnl,
-            pnl_pct,
-        )
+            self.realized_pnl(),
+            unrealized,
+            unrealized_pct,
+            self.total_pnl(),
+            self.fees_usd,
+        );
+
+        let baskets = self.baskets();
+        if !baskets.is_empty() {
+            let locked: f64 = baskets.iter().map(|b| b.locked_profit).sum();
+            summary.push_str(&format!(" | 🔒 {} hedged baskets, locked P/L at resolution: ${:.2}", baskets.len(), locked));
+        }
+        summary
     }
 }
 
@@ -176,6 +552,9 @@ pub struct ExitSignal {
     pub token_id: String,
     pub question: String,
     pub side: String,
+    pub outcome_index: usize,
+    pub url: String,
+    pub neg_risk: bool,
     pub signal_type: ExitType,
     pub shares_to_sell: f64,
     pub current_price: f64,
@@ -183,17 +562,177 @@ pub struct ExitSignal {
     pub pnl_pct: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExitType {
-    FullExit,
-    PartialExit,
+    /// Take-profit ladder tiers reached; `multiple` is the highest of them
+    TakeProfit { multiple: f64, tiers: Vec<TakeProfitTier> },
+    StopLoss,
+    TrailingStop,
+    MaxHoldingPeriod,
+    PreResolution,
+}
+
+impl ExitType {
+    /// Stable name used in the trade journal
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExitType::TakeProfit { .. } => "take_profit",
+            ExitType::StopLoss => "stop_loss",
+            ExitType::TrailingStop => "trailing_stop",
+            ExitType::MaxHoldingPeriod => "max_holding_period",
+            ExitType::PreResolution => "pre_resolution",
+        }
+    }
+
+    /// Exits that cut a loss and should sell into the bid rather than wait at the mid
+    pub fn is_protective(&self) -> bool {
+        matches!(self, ExitType::StopLoss | ExitType::TrailingStop)
+    }
 }
 
 impl std::fmt::Display for ExitType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ExitType::FullExit => write!(f, "🎯 Full Exit"),
-            ExitType::PartialExit => write!(f, "🔄 Partial Exit (50%)"),
+            ExitType::TakeProfit { multiple, .. } => write!(f, "🎯 Take-Profit ({}x)", multiple),
+            ExitType::StopLoss => write!(f, "🛑 Stop-Loss"),
+            ExitType::TrailingStop => write!(f, "📉 Trailing Stop"),
+            ExitType::MaxHoldingPeriod => write!(f, "⌛ Max Holding Period"),
+            ExitType::PreResolution => write!(f, "⏰ Pre-Resolution Exit"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lot(shares: f64, price: f64, fee_usd: f64) -> Lot {
+        Lot { shares, price, fee_usd, acquired_at: String::new() }
+    }
+
+    /// 100 shares @ $0.40 ($1 fee) then 50 @ $0.60 ($0.50 fee), marked at $0.50
+    fn position() -> Position {
+        let mut pos = Position {
+            condition_id: "cond".into(),
+            token_id: "tok".into(),
+            current_price: 0.5,
+            lots: vec![lot(100.0, 0.4, 1.0), lot(50.0, 0.6, 0.5)],
+            ..Default::default()
+        };
+        pos.refresh();
+        pos
+    }
+
+    fn tracker() -> PositionTracker {
+        let mut tracker = PositionTracker::new();
+        tracker.import(position());
+        tracker
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn refresh_sums_lots_and_nets_open_fees() {
+        let pos = position();
+        assert_close(pos.shares, 150.0);
+        assert_close(pos.cost_usd, 70.0);
+        assert_close(pos.entry_price, 70.0 / 150.0);
+        assert_close(pos.current_value, 75.0);
+        assert_close(pos.pnl, 75.0 - 70.0 - 1.5);
+        assert_close(pos.pnl_pct, 3.5 / 70.0 * 100.0);
+    }
+
+    #[test]
+    fn consume_lots_takes_oldest_first() {
+        let mut pos = position();
+        let (cost, fees) = pos.consume_lots(120.0);
+        assert_close(cost, 100.0 * 0.4 + 20.0 * 0.6);
+        assert_close(fees, 1.0 + 0.5 * 20.0 / 50.0);
+        assert_eq!(pos.lots.len(), 1);
+        assert_close(pos.lots[0].shares, 30.0);
+        assert_close(pos.lots[0].fee_usd, 0.3);
+        assert_close(pos.shares, 30.0);
+        assert_close(pos.entry_price, 0.6);
+    }
+
+    #[test]
+    fn consume_lots_beyond_holdings_empties_position() {
+        let mut pos = position();
+        let (cost, fees) = pos.consume_lots(500.0);
+        assert_close(cost, 70.0);
+        assert_close(fees, 1.5);
+        assert!(pos.lots.is_empty());
+        assert_close(pos.shares, 0.0);
+    }
+
+    #[test]
+    fn apply_sell_realizes_fifo_pnl() {
+        let mut tracker = tracker();
+        let result = tracker.apply_sell("tok", 100.0, 0.7, 0.25).unwrap();
+        assert_eq!(result.condition_id, "cond");
+        assert_close(result.cost_released, 40.0);
+        assert!(!result.closed);
+
+        // 70 proceeds - 40 cost - 1 buy fee - 0.25 sell fee
+        assert_close(tracker.realized_pnl(), 28.75);
+        let pos = &tracker.positions()[0];
+        assert_close(pos.realized_pnl, 28.75);
+        assert_close(pos.fees_usd, 0.25);
+        assert_close(pos.shares, 50.0);
+    }
+
+    #[test]
+    fn apply_sell_of_everything_closes_position() {
+        let mut tracker = tracker();
+        let result = tracker.apply_sell("tok", 200.0, 0.5, 0.0).unwrap();
+        assert!(result.closed);
+        assert_close(result.cost_released, 70.0);
+        assert_close(tracker.realized_pnl(), 75.0 - 70.0 - 1.5);
+        assert!(tracker.positions().is_empty());
+        assert!(tracker.apply_sell("tok", 1.0, 0.5, 0.0).is_none());
+    }
+
+    #[test]
+    fn settle_pays_out_remaining_shares() {
+        let mut tracker = tracker();
+        tracker.apply_sell("tok", 100.0, 0.7, 0.0);
+        let settlement = tracker.settle("tok", 1.0).unwrap();
+        assert_eq!(settlement.outcome_label(), "Won");
+        assert_close(settlement.proceeds, 50.0);
+        assert_close(settlement.pnl, 50.0 - 30.0 - 0.5);
+        assert!(settlement.basket.is_none());
+        assert_close(tracker.realized_pnl(), 29.0 + 19.5);
+        assert!(tracker.positions().is_empty());
+    }
+
+    #[test]
+    fn settle_lost_and_split_markets() {
+        let lost = tracker().settle("tok", 0.0).unwrap();
+        assert_eq!(lost.outcome_label(), "Lost");
+        assert_close(lost.pnl, -71.5);
+
+        let split = tracker().settle("tok", 0.5).unwrap();
+        assert_eq!(split.outcome_label(), "Split");
+        assert_close(split.pnl, 75.0 - 71.5);
+    }
+
+    #[test]
+    fn settle_reports_basket_after_last_leg() {
+        let mut tracker = PositionTracker::new();
+        for token_id in ["a", "b"] {
+            tracker.import(Position {
+                token_id: token_id.into(),
+                basket_id: Some("basket".into()),
+                lots: vec![lot(10.0, 0.45, 0.0)],
+                ..Default::default()
+            });
         }
+        assert!(tracker.settle("a", 1.0).unwrap().basket.is_none());
+        let basket = tracker.settle("b", 0.0).unwrap().basket.unwrap();
+        assert_eq!(basket.legs, 2);
+        assert_close(basket.cost_usd, 9.0);
+        assert_close(basket.proceeds, 10.0);
     }
 }
@@ -0,0 +1,313 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::clob::OrderSide;
+use crate::config::Config;
+use crate::executor::{Executor, Trade, TradeStatus};
+use crate::journal::TradeJournal;
+use crate::positions::{Lot, Position, PositionTracker};
+use crate::risk::RiskManager;
+
+const DATA_API_URL: &str = "https://data-api.polymarket.com";
+/// Positions fetched per data API page
+const POSITIONS_PAGE_SIZE: usize = 500;
+/// Position pages read per run
+const MAX_POSITION_PAGES: usize = 20;
+/// Trades fetched per data API page
+const TRADES_PAGE_SIZE: usize = 500;
+/// Trade history pages read per run
+const MAX_TRADE_PAGES: usize = 10;
+/// Share differences below this are rounding, not discrepancies
+const SHARE_TOLERANCE: f64 = 0.5;
+
+/// A position held by the wallet, as reported by the data API
+#[derive(Debug, Deserialize)]
+struct ApiPosition {
+    #[serde(default)]
+    asset: String,
+    #[serde(rename = "conditionId", default)]
+    condition_id: String,
+    #[serde(default)]
+    size: f64,
+    #[serde(rename = "avgPrice", default)]
+    avg_price: f64,
+    #[serde(rename = "curPrice", default)]
+    cur_price: f64,
+    #[serde(default)]
+    redeemable: bool,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    slug: String,
+    #[serde(default)]
+    outcome: String,
+    #[serde(rename = "outcomeIndex", default)]
+    outcome_index: usize,
+    #[serde(rename = "endDate", default)]
+    end_date: Option<String>,
+    #[serde(rename = "negativeRisk", default)]
+    negative_risk: bool,
+}
+
+/// A fill in the wallet's trade history, as reported by the data API
+#[derive(Debug, Deserialize)]
+struct ApiTrade {
+    #[serde(default)]
+    side: String,
+    #[serde(default)]
+    asset: String,
+    #[serde(default)]
+    size: f64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    timestamp: i64,
+}
+
+/// Periodically compares the bot's holdings with what the trading wallet actually holds
+pub struct Reconciler {
+    client: reqwest::Client,
+    last_run: Option<Instant>,
+    /// Discrepancies found by the previous run, so persistent ones are reported once
+    previous: HashSet<String>,
+}
+
+impl Reconciler {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+            last_run: None,
+            previous: HashSet::new(),
+        }
+    }
+
+    /// Whether `reconcile_interval_secs` has passed since the last run
+    pub fn is_due(&self, config: &Config) -> bool {
+        config.reconcile_interval_secs > 0
+            && self.last_run.is_none_or(|t| t.elapsed().as_secs() >= config.reconcile_interval_secs)
+    }
+
+    /// Diff the wallet's positions and trade history against the tracker and trade journal,
+    /// import positions opened outside the bot, and resync risk limits. Returns the discrepancies
+    /// that are new or changed since the previous run.
+    pub async fn run(
+        &mut self,
+        address: &str,
+        config: &Config,
+        position_tracker: &mut PositionTracker,
+        executor: &Executor,
+        risk_manager: &mut RiskManager,
+    ) -> Result<Vec<String>> {
+        self.last_run = Some(Instant::now());
+        let positions = self.fetch_positions(address).await?;
+        let trades = self.fetch_trades(address).await?;
+        let mut discrepancies = Vec::new();
+        let journal = match journal_trades(config) {
+            Ok(journal) => journal,
+            Err(e) => {
+                discrepancies.push(format!("Could not read trade journal: {}", e));
+                Vec::new()
+            }
+        };
+        let open_orders: Vec<&Trade> = executor.open_buy_orders().collect();
+
+        // Resolved positions are settled by the bot and redeemed on chain, not tracked
+        let held: HashMap<&str, &ApiPosition> = positions.iter()
+            .filter(|p| !p.redeemable && p.size > 0.0)
+            .map(|p| (p.asset.as_str(), p))
+            .collect();
+        let redeemable: HashSet<&str> = positions.iter()
+            .filter(|p| p.redeemable)
+            .map(|p| p.asset.as_str())
+            .collect();
+
+        // A tracked position whose market resolved is still held until the bot settles it
+        for pos in position_tracker.positions().iter().filter(|p| !redeemable.contains(p.token_id.as_str())) {
+            match held.get(pos.token_id.as_str()) {
+                None => discrepancies.push(format!(
+                    "Tracked {:.0} {} {} not held by wallet", pos.shares, pos.side, pos.question)),
+                Some(actual) if (actual.size - pos.shares).abs() > SHARE_TOLERANCE => discrepancies.push(format!(
+                    "{} {}: tracked {:.0} shares, wallet holds {:.0}", pos.side, pos.question, pos.shares, actual.size)),
+                Some(_) => {}
+            }
+        }
+
+        // Tokens the bot traded may be untracked only because a fill hasn't been seen yet;
+        // importing them would double the lots once it arrives
+        let bot_tokens: HashSet<&str> = journal.iter().map(|t| t.token_id.as_str())
+            .chain(open_orders.iter().map(|t| t.token_id.as_str()))
+            .collect();
+        let tracked: HashSet<String> = position_tracker.positions().iter().map(|p| p.token_id.clone()).collect();
+        for actual in held.values().filter(|p| !tracked.contains(&p.asset)) {
+            if bot_tokens.contains(actual.asset.as_str()) {
+                discrepancies.push(format!("Wallet holds {:.0} {} {} traded by the bot but not tracked (not imported)",
+                    actual.size, actual.outcome, actual.title));
+                continue;
+            }
+            discrepancies.push(format!("Imported {:.0} {} {} opened outside the bot @ ${:.4}",
+                actual.size, actual.outcome, actual.title, actual.avg_price));
+            position_tracker.import(imported_position(actual));
+        }
+
+        discrepancies.extend(compare_journal(&journal, &trades));
+
+        // Risk limits follow the reconciled holdings plus capital reserved by open orders,
+        // keyed like the exposure recorded at entry (basket ID for basket legs)
+        let mut market_exposure: HashMap<String, f64> = HashMap::new();
+        for pos in position_tracker.positions() {
+            let key = pos.basket_id.as_deref().unwrap_or(&pos.condition_id);
+            *market_exposure.entry(key.to_string()).or_insert(0.0) += pos.cost_usd;
+        }
+        for order in &open_orders {
+            let key = order.basket_id.as_deref().unwrap_or(&order.condition_id);
+            let reserved = (order.size - order.filled_size).max(0.0) * order.price;
+            *market_exposure.entry(key.to_string()).or_insert(0.0) += reserved;
+        }
+        risk_manager.sync_positions(market_exposure);
+
+        let current: HashSet<String> = discrepancies.into_iter().collect();
+        let new: Vec<String> = current.iter().filter(|d| !self.previous.contains(*d)).cloned().collect();
+        info!("🔎 Reconciled {} wallet positions and {} trades: {} discrepancies ({} new)",
+            held.len(), trades.len(), current.len(), new.len());
+        for discrepancy in &current {
+            if new.contains(discrepancy) {
+                warn!("🔎 {}", discrepancy);
+            } else {
+                debug!("🔎 Still: {}", discrepancy);
+            }
+        }
+        self.previous = current;
+        Ok(new)
+    }
+
+    async fn fetch_positions(&self, address: &str) -> Result<Vec<ApiPosition>> {
+        let mut positions = Vec::new();
+        for page in 0..MAX_POSITION_PAGES {
+            let batch: Vec<ApiPosition> = self.client
+                .get(format!("{}/positions", DATA_API_URL))
+                .query(&[
+                    ("user", address.to_string()),
+                    ("sizeThreshold", "0.01".to_string()),
+                    ("limit", POSITIONS_PAGE_SIZE.to_string()),
+                    ("offset", (page * POSITIONS_PAGE_SIZE).to_string()),
+                ])
+                .send()
+                .await
+                .context("Failed to fetch wallet positions")?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Invalid positions response")?;
+            let done = batch.len() < POSITIONS_PAGE_SIZE;
+            positions.extend(batch);
+            if done {
+                return Ok(positions);
+            }
+        }
+        warn!("Wallet has more than {} pages of positions - reconciling the first {}", MAX_POSITION_PAGES, positions.len());
+        Ok(positions)
+    }
+
+    async fn fetch_trades(&self, address: &str) -> Result<Vec<ApiTrade>> {
+        let mut trades = Vec::new();
+        for page in 0..MAX_TRADE_PAGES {
+            let batch: Vec<ApiTrade> = self.client
+                .get(format!("{}/trades", DATA_API_URL))
+                .query(&[
+                    ("user", address.to_string()),
+                    ("takerOnly", "false".to_string()),
+                    ("limit", TRADES_PAGE_SIZE.to_string()),
+                    ("offset", (page * TRADES_PAGE_SIZE).to_string()),
+                ])
+                .send()
+                .await
+                .context("Failed to fetch wallet trades")?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Invalid trades response")?;
+            let done = batch.len() < TRADES_PAGE_SIZE;
+            trades.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(trades)
+    }
+}
+
+/// Tracker position for shares bought outside the bot, as one lot at the wallet's average price
+fn imported_position(actual: &ApiPosition) -> Position {
+    let now = Utc::now().to_rfc3339();
+    Position {
+        condition_id: actual.condition_id.clone(),
+        token_id: actual.asset.clone(),
+        question: actual.title.clone(),
+        side: actual.outcome.clone(),
+        outcome_index: actual.outcome_index,
+        entry_price: actual.avg_price,
+        current_price: actual.cur_price,
+        url: format!("https://polymarket.com/market/{}", actual.slug),
+        entered_at: now.clone(),
+        lots: vec![Lot { shares: actual.size, price: actual.avg_price, fee_usd: 0.0, acquired_at: now }],
+        initial_shares: actual.size,
+        high_water_price: actual.cur_price.max(actual.avg_price),
+        neg_risk: actual.negative_risk,
+        end_date: actual.end_date.clone(),
+        ..Default::default()
+    }
+}
+
+/// Latest state of each live journal trade; empty without a journal
+fn journal_trades(config: &Config) -> Result<Vec<Trade>> {
+    let Some(path) = config.trade_journal_path.as_deref().filter(|p| Path::new(p).exists()) else {
+        return Ok(Vec::new());
+    };
+    let mut latest = HashMap::new();
+    for entry in TradeJournal::load(Path::new(path))? {
+        latest.insert(entry.trade.id.clone(), entry.trade);
+    }
+    Ok(latest.into_values().filter(|t| t.status != TradeStatus::PaperTrade).collect())
+}
+
+/// Flag tokens whose net traded shares in the wallet history differ from the journal's fills.
+/// When the history was cut off, only journal trades placed after its oldest fill are compared.
+fn compare_journal(journal: &[Trade], trades: &[ApiTrade]) -> Vec<String> {
+    let max_trades = MAX_TRADE_PAGES * TRADES_PAGE_SIZE;
+    let history_from = trades.iter().map(|t| t.timestamp).min().filter(|_| trades.len() >= max_trades);
+    let window = journal.iter().filter(|t| {
+        let placed = DateTime::parse_from_rfc3339(&t.placed_at).map_or(i64::MAX, |d| d.timestamp());
+        history_from.is_none_or(|from| placed >= from)
+    });
+
+    let mut journal_net: HashMap<String, (f64, String)> = HashMap::new();
+    for trade in window {
+        let signed = if trade.order_side == OrderSide::Sell { -trade.filled_size } else { trade.filled_size };
+        journal_net.entry(trade.token_id.clone()).or_insert((0.0, trade.question.clone())).0 += signed;
+    }
+    let mut wallet_net: HashMap<String, (f64, String)> = HashMap::new();
+    for trade in trades {
+        let signed = if trade.side.eq_ignore_ascii_case("SELL") { -trade.size } else { trade.size };
+        wallet_net.entry(trade.asset.clone()).or_insert((0.0, trade.title.clone())).0 += signed;
+    }
+
+    let tokens: HashSet<&String> = journal_net.keys().chain(wallet_net.keys()).collect();
+    tokens.into_iter()
+        .filter_map(|token| {
+            let (journal, journal_title) = journal_net.get(token).cloned().unwrap_or_default();
+            let (wallet, wallet_title) = wallet_net.get(token).cloned().unwrap_or_default();
+            ((wallet - journal).abs() > SHARE_TOLERANCE).then(|| {
+                let title = if wallet_title.is_empty() { journal_title } else { wallet_title };
+                format!("{}: wallet history nets {:.0} shares, journal {:.0}", title, wallet, journal)
+            })
+        })
+        .collect()
+}
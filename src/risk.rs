@@ -27,13 +27,7 @@ impl RiskManager {
 
     /// Check if a trade is allowed under current risk limits
     pub fn check_trade(&mut self, opp: &MarketOpportunity, trade_amount: f64, config: &Config) -> Result<f64> {
-        // Reset daily counter if new day
-        let today = Utc::now().date_naive();
-        if today != self.daily_reset_date {
-            info!("📆 New day - resetting daily spend counter");
-            self.daily_spent = 0.0;
-            self.daily_reset_date = today;
-        }
+        self.roll_day();
 
         // 1. Check daily spending limit
         if self.daily_spent + trade_amount > config.max_daily_spend_usd {
@@ -83,6 +77,30 @@ impl RiskManager {
         Ok(final_amount)
     }
 
+    /// Reset the daily counter if a new day has started
+    fn roll_day(&mut self) {
+        let today = Utc::now().date_naive();
+        if today != self.daily_reset_date {
+            info!("📆 New day - resetting daily spend counter");
+            self.daily_spent = 0.0;
+            self.daily_reset_date = today;
+        }
+    }
+
+    /// USDC spent today
+    pub fn daily_spent(&mut self) -> f64 {
+        self.roll_day();
+        self.daily_spent
+    }
+
+    /// Capital that can still be committed under the daily and total exposure limits
+    pub fn remaining_budget(&mut self, config: &Config) -> f64 {
+        self.roll_day();
+        let daily = config.max_daily_spend_usd - self.daily_spent;
+        let exposure = config.max_total_exposure_usd - self.total_exposure;
+        daily.min(exposure).max(0.0)
+    }
+
     /// Record a trade was made
     pub fn record_trade(&mut self, condition_id: &str, amount: f64) {
         self.daily_spent += amount;
@@ -98,10 +116,51 @@ impl RiskManager {
         self.market_exposure.remove(condition_id);
     }
 
-    /// Update position count from actual data
-    pub fn sync_positions(&mut self, count: usize, total_exposure: f64) {
-        self.open_position_count = count;
-        self.total_exposure = total_exposure;
+    /// Release the cost basis of shares sold out of a position that stays open
+    pub fn reduce_exposure(&mut self, condition_id: &str, amount: f64) {
+        self.total_exposure = (self.total_exposure - amount).max(0.0);
+        if let Some(exposure) = self.market_exposure.get_mut(condition_id) {
+            *exposure = (*exposure - amount).max(0.0);
+        }
+    }
+
+    /// Restore exposure for shares put back into a position after a failed sell;
+    /// `reopened` when the sell had closed the position
+    pub fn restore_exposure(&mut self, condition_id: &str, amount: f64, reopened: bool) {
+        self.total_exposure += amount;
+        *self.market_exposure.entry(condition_id.to_string()).or_insert(0.0) += amount;
+        if reopened {
+            self.open_position_count += 1;
+        }
+    }
+
+    /// Release capital reserved for an order that was cancelled before filling completely.
+    /// Only a reservation made today (`reserved_on`) is taken back out of the daily spend.
+    pub fn release_unfilled(&mut self, condition_id: &str, amount: f64, nothing_filled: bool, reserved_on: Option<NaiveDate>) {
+        self.roll_day();
+        if reserved_on == Some(self.daily_reset_date) {
+            self.daily_spent = (self.daily_spent - amount).max(0.0);
+        }
+        self.total_exposure = (self.total_exposure - amount).max(0.0);
+        if let Some(exposure) = self.market_exposure.get_mut(condition_id) {
+            *exposure = (*exposure - amount).max(0.0);
+        }
+        if nothing_filled {
+            self.open_position_count = self.open_position_count.saturating_sub(1);
+            self.market_exposure.remove(condition_id);
+        }
+    }
+
+    /// Replace position count and exposure with reconciled per-market exposure
+    pub fn sync_positions(&mut self, market_exposure: std::collections::HashMap<String, f64>) {
+        self.open_position_count = market_exposure.len();
+        self.total_exposure = market_exposure.values().sum();
+        self.market_exposure = market_exposure;
+    }
+
+    /// Capital committed to open positions and orders
+    pub fn total_exposure(&self) -> f64 {
+        self.total_exposure
     }
 
     /// Get risk summary
@@ -114,3 +173,39 @@ impl RiskManager {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_returns_daily_spend_only_for_todays_reservations() {
+        let today = Utc::now().date_naive();
+        let mut risk = RiskManager::new();
+        risk.record_trade("a", 10.0);
+        risk.record_trade("b", 10.0);
+
+        risk.release_unfilled("a", 4.0, false, Some(today));
+        assert!((risk.daily_spent() - 16.0).abs() < 1e-9);
+        assert!((risk.total_exposure() - 16.0).abs() < 1e-9);
+
+        // Placed before midnight: exposure is freed but today's spend is untouched
+        risk.release_unfilled("b", 10.0, true, today.pred_opt());
+        assert!((risk.daily_spent() - 16.0).abs() < 1e-9);
+        assert!((risk.total_exposure() - 6.0).abs() < 1e-9);
+        assert!(!risk.market_exposure.contains_key("b"));
+    }
+
+    #[test]
+    fn release_after_midnight_lands_on_the_new_day() {
+        let mut risk = RiskManager::new();
+        risk.record_trade("a", 10.0);
+        let yesterday = risk.daily_reset_date.pred_opt().unwrap();
+        risk.daily_reset_date = yesterday;
+
+        risk.release_unfilled("a", 10.0, true, Some(yesterday));
+        assert_eq!(risk.daily_reset_date, Utc::now().date_naive());
+        assert_eq!(risk.daily_spent(), 0.0);
+        assert_eq!(risk.total_exposure(), 0.0);
+    }
+}
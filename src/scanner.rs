@@ -1,10 +1,20 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use tracing::{info, warn, debug};
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::Arc;
+use futures::stream::{self, StreamExt};
+use tracing::debug;
 use std::collections::HashMap;
 
 use crate::config::Config;
+use crate::gamma::{GammaClient, GammaMarket, MarketQuery, MarketSnapshot};
+use crate::positions::Position;
+use crate::scoring::{self, ScoreBreakdown, ScoreInputs};
+
+/// Maximum condition IDs per Gamma price refresh request
+const PRICE_BATCH_SIZE: usize = 50;
+/// Maximum in-flight Gamma price refresh requests
+const PRICE_FETCH_CONCURRENCY: usize = 4;
 
 /// Represents a discovered market opportunity
 #[derive(Debug, Clone, Serialize)]
@@ -15,25 +25,103 @@ pub struct MarketOpportunity {
     pub slug: String,
     pub event_slug: String,
     pub category: String,
-    pub yes_price: f64,
-    pub no_price: f64,
+    /// Lowercased Gamma tag labels/slugs and categories for the market and its events
+    pub tags: Vec<String>,
+    /// Label of the outcome to buy (e.g. "Yes", "Lakers")
+    pub outcome: String,
+    /// Index of that outcome in `outcomes` / `clobTokenIds`
+    pub outcome_index: usize,
+    /// Current price of the outcome to buy
+    pub price: f64,
+    pub outcomes: Vec<String>,
+    pub outcome_prices: Vec<f64>,
     pub liquidity: f64,
     pub volume_24h: f64,
     pub volume_total: f64,
     pub end_date: Option<String>,
+    /// Days until scheduled resolution (negative once the end date has passed)
+    pub days_to_resolution: Option<f64>,
+    /// Upside multiple if the outcome wins, scaled to a one-year lockup
+    pub annualized_upside: Option<f64>,
     pub url: String,
+    /// Highest-scoring source that found this opportunity
     pub discovery_type: DiscoveryType,
+    /// Every source that found this market this cycle
+    pub reasons: Vec<DiscoveryType>,
     pub created_at: Option<String>,
     pub score: f64,
+    /// Points per scoring factor; sums to `score`
+    pub score_breakdown: ScoreBreakdown,
+    pub neg_risk: bool,
+    /// Outcome tokens bought together for multi-leg (arbitrage) opportunities; empty otherwise.
+    /// For baskets, `price` is the cost of one share of every leg.
+    pub legs: Vec<OpportunityLeg>,
+    /// Guaranteed payout per basket share at resolution (multi-leg only)
+    pub guaranteed_payout: Option<f64>,
+    /// Shares available at the quoted price(s), when sized against the order book
+    pub max_shares: Option<f64>,
+    /// Probability the outcome resolves YES according to the trained model
+    pub predicted_probability: Option<f64>,
+    /// Model-implied return per dollar staked if held to resolution
+    pub expected_value: Option<f64>,
+}
+
+/// One outcome token of a multi-leg opportunity
+#[derive(Debug, Clone, Serialize)]
+pub struct OpportunityLeg {
+    pub condition_id: String,
+    pub token_id: String,
+    pub question: String,
+    pub outcome: String,
+    pub outcome_index: usize,
+    pub price: f64,
     pub neg_risk: bool,
 }
 
+impl MarketOpportunity {
+    /// Whether this opportunity buys a hedged basket of outcomes
+    pub fn is_basket(&self) -> bool {
+        !self.legs.is_empty()
+    }
+
+    /// Replace the score with a freshly computed breakdown
+    pub fn set_score(&mut self, breakdown: ScoreBreakdown) {
+        self.score = breakdown.total();
+        self.score_breakdown = breakdown;
+    }
+
+    /// Add points for a named factor on top of the current score
+    pub fn add_score(&mut self, factor: &str, points: f64) {
+        self.score += points;
+        self.score_breakdown.add(factor, points);
+    }
+
+    /// All discovery reasons joined for display
+    pub fn reasons_label(&self) -> String {
+        self.reasons.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(" + ")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum DiscoveryType {
     NewMarket,
     Longshot,
     VolumeSurge,
-    Mispriced,
+    BinaryArbitrage,
+    EventArbitrage,
+}
+
+impl DiscoveryType {
+    /// Key used for this type in `scoring.discovery_bonus`
+    pub fn key(&self) -> &'static str {
+        match self {
+            DiscoveryType::NewMarket => "new_market",
+            DiscoveryType::Longshot => "longshot",
+            DiscoveryType::VolumeSurge => "volume_surge",
+            DiscoveryType::BinaryArbitrage => "binary_arbitrage",
+            DiscoveryType::EventArbitrage => "event_arbitrage",
+        }
+    }
 }
 
 impl std::fmt::Display for DiscoveryType {
@@ -42,447 +130,220 @@ impl std::fmt::Display for DiscoveryType {
             DiscoveryType::NewMarket => write!(f, "🆕 New Market"),
             DiscoveryType::Longshot => write!(f, "🎯 Longshot"),
             DiscoveryType::VolumeSurge => write!(f, "📈 Volume Surge"),
-            DiscoveryType::Mispriced => write!(f, "⚡ Mispriced"),
+            DiscoveryType::BinaryArbitrage => write!(f, "⚖️ Binary Arbitrage"),
+            DiscoveryType::EventArbitrage => write!(f, "🧺 Event Arbitrage"),
         }
     }
 }
 
-/// Raw market data from Gamma API
-#[derive(Debug, Deserialize, Default, Clone)]
-pub struct GammaMarket {
-    #[serde(default)]
-    pub id: String,
-    #[serde(default)]
-    pub question: String,
-    #[serde(default)]
-    pub slug: Option<String>,
-    #[serde(rename = "conditionId", default)]
-    pub condition_id: String,
-    #[serde(rename = "outcomePrices", default)]
-    pub outcome_prices: Option<String>,
-    #[serde(default)]
-    pub liquidity: Option<String>,
-    #[serde(default)]
-    pub volume: Option<String>,
-    #[serde(default)]
-    pub active: bool,
-    #[serde(default)]
-    pub closed: bool,
-    #[serde(default)]
-    pub resolved: Option<bool>,
-    #[serde(rename = "endDateIso", default)]
-    pub end_date_iso: Option<String>,
-    #[serde(rename = "createdAt", default)]
-    pub created_at: Option<String>,
-    #[serde(rename = "volume24hr", default)]
-    pub volume_24hr: Option<f64>,
-    #[serde(rename = "clobTokenIds", default)]
-    pub clob_token_ids: Option<String>,
-    #[serde(default)]
-    pub events: Vec<GammaEvent>,
-    #[serde(rename = "acceptingOrders", default)]
-    pub accepting_orders: bool,
-    #[serde(rename = "negRisk", default)]
-    pub neg_risk: bool,
-}
-
-#[derive(Debug, Deserialize, Default, Clone)]
-pub struct GammaEvent {
-    #[serde(default)]
-    pub slug: Option<String>,
-    #[serde(default)]
-    pub title: Option<String>,
+/// Price refresh for tracked positions, keyed by token_id
+#[derive(Debug, Default)]
+pub struct PositionPrices {
+    pub prices: Vec<(String, f64)>,
+    /// Settlement price of positions whose market resolved (1.0 won, 0.0 lost, 0.5 split)
+    pub resolved: Vec<(String, f64)>,
+    /// Hours since close of positions whose market closed without settling yet
+    pub unsettled: Vec<(String, f64)>,
 }
 
+/// Fetches shared market data and prices for tracked positions
 pub struct Scanner {
-    client: reqwest::Client,
-    known_market_ids: std::collections::HashSet<String>,
-    last_full_scan: Option<DateTime<Utc>>,
-    /// Track previous volume for spike detection
-    volume_history: HashMap<String, f64>,
+    gamma: GammaClient,
 }
 
 impl Scanner {
     pub fn new() -> Self {
         Self {
-            client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .unwrap(),
-            known_market_ids: std::collections::HashSet::new(),
-            last_full_scan: None,
-            volume_history: HashMap::new(),
+            gamma: GammaClient::new(),
         }
     }
 
-    /// Scan for new markets created recently
-    pub async fn scan_new_markets(&mut self, config: &Config) -> Result<Vec<MarketOpportunity>> {
-        info!("🔍 Scanning for new markets...");
-        let mut opportunities = Vec::new();
-
-        let url = "https://gamma-api.polymarket.com/markets?limit=100&closed=false&order=createdAt&ascending=false";
+    /// Fetch (or reuse) the shared market snapshot for this cycle
+    pub async fn refresh_snapshot(&mut self, config: &Config, full: bool, with_events: bool) -> Result<Arc<MarketSnapshot>> {
+        self.gamma.snapshot(config, full, with_events).await
+    }
 
-        let response = self.client.get(url).send().await?;
-        if response.status() != 200 {
-            warn!("Gamma API returned {}", response.status());
-            return Ok(opportunities);
+    /// Fetch current prices of the outcome tokens held by each position, keyed by token_id,
+    /// and the settlement price of those whose market has resolved.
+    /// Condition IDs are queried in multi-ID batches with bounded concurrency.
+    pub async fn fetch_current_prices(&self, positions: &[Position]) -> Result<PositionPrices> {
+        if positions.is_empty() {
+            return Ok(PositionPrices::default());
         }
 
-        let markets: Vec<GammaMarket> = response.json().await?;
-        info!("📊 Fetched {} recent markets", markets.len());
-
-        for market in markets {
-            if market.closed || market.resolved.unwrap_or(false) || !market.accepting_orders {
-                continue;
-            }
-
-            // Check if this is a new market we haven't seen
-            let is_new = !self.known_market_ids.contains(&market.id);
-
-            if let Some(opp) = self.evaluate_market(&market, config, if is_new { DiscoveryType::NewMarket } else { DiscoveryType::Longshot }) {
-                if is_new || opp.yes_price <= config.max_price_decimal() {
-                    opportunities.push(opp);
+        let mut condition_ids: Vec<&str> = positions.iter().map(|p| p.condition_id.as_str()).collect();
+        condition_ids.sort_unstable();
+        condition_ids.dedup();
+
+        let batches: Vec<Result<Vec<GammaMarket>>> = stream::iter(condition_ids.chunks(PRICE_BATCH_SIZE))
+            .map(|chunk| self.fetch_markets_by_condition(chunk))
+            .buffer_unordered(PRICE_FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut markets: HashMap<String, GammaMarket> = HashMap::new();
+        for batch in batches {
+            match batch {
+                Ok(fetched) => {
+                    for market in fetched {
+                        markets.insert(market.condition_id.clone(), market);
+                    }
                 }
+                Err(e) => debug!("Failed to fetch price batch: {}", e),
             }
-
-            self.known_market_ids.insert(market.id.clone());
         }
 
-        info!("✅ Found {} opportunities from new market scan", opportunities.len());
-        Ok(opportunities)
-    }
-
-    /// Full scan for longshot markets (low-price high-upside)
-    pub async fn scan_longshots(&mut self, config: &Config) -> Result<Vec<MarketOpportunity>> {
-        info!("🎯 Scanning for longshot markets...");
-        let mut opportunities = Vec::new();
-        let mut offset = 0;
-        let limit = 100;
-        let max_pages = 20;
-
-        for page in 0..max_pages {
-            let url = format!(
-                "https://gamma-api.polymarket.com/markets?limit={}&offset={}&closed=false&order=volume24hr&ascending=false",
-                limit, offset
-            );
-
-            let response = self.client.get(&url).send().await?;
-            if response.status() != 200 {
-                break;
-            }
-
-            let markets: Vec<GammaMarket> = response.json().await?;
-            if markets.is_empty() {
-                break;
+        let mut updates = PositionPrices::default();
+        for pos in positions {
+            let Some(market) = markets.get(&pos.condition_id) else { continue };
+            if let Some(payouts) = market.final_payouts() {
+                let payout = payouts.get(held_outcome_index(market, pos)).copied().unwrap_or(0.0);
+                updates.resolved.push((pos.token_id.clone(), payout));
+                continue;
             }
-
-            debug!("Longshot scan page {} - {} markets", page + 1, markets.len());
-
-            for market in &markets {
-                if market.closed || market.resolved.unwrap_or(false) || !market.accepting_orders {
-                    continue;
-                }
-
-                if let Some(opp) = self.evaluate_market(market, config, DiscoveryType::Longshot) {
-                    if opp.yes_price <= config.max_price_decimal() && opp.liquidity >= config.min_liquidity_usd {
-                        opportunities.push(opp);
-                    }
-                }
-
-                self.known_market_ids.insert(market.id.clone());
+            if market.closed {
+                let closed_hours = market.closed_at()
+                    .map(|t| Utc::now().signed_duration_since(t).num_minutes() as f64 / 60.0)
+                    .unwrap_or(0.0);
+                updates.unsettled.push((pos.token_id.clone(), closed_hours));
             }
-
-            offset += limit;
-            if markets.len() < limit as usize {
-                break;
+            if let Some(price) = held_outcome_price(market, pos) {
+                updates.prices.push((pos.token_id.clone(), price));
             }
         }
 
-        self.last_full_scan = Some(Utc::now());
-        info!("✅ Found {} longshot opportunities", opportunities.len());
-        Ok(opportunities)
+        Ok(updates)
     }
 
-    /// Evaluate a single market for opportunity potential
-    fn evaluate_market(&self, market: &GammaMarket, config: &Config, discovery_type: DiscoveryType) -> Option<MarketOpportunity> {
-        // Parse outcome prices
-        let prices_str = market.outcome_prices.as_ref()?;
-        let prices: Vec<String> = serde_json::from_str(prices_str).ok()?;
-        if prices.len() < 2 {
-            return None;
-        }
-
-        let yes_price: f64 = prices[0].parse().ok()?;
-        let no_price: f64 = prices[1].parse().ok()?;
-
-        // Skip zero-price or fully-priced markets
-        if yes_price <= 0.001 || yes_price >= 0.99 {
-            return None;
-        }
-
-        // Skip if above max price
-        if yes_price > config.max_price_decimal() && no_price > config.max_price_decimal() {
-            return None;
-        }
-
-        let liquidity: f64 = market.liquidity.as_ref()
-            .and_then(|l| l.parse().ok())
-            .unwrap_or(0.0);
-
-        let volume_total: f64 = market.volume.as_ref()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(0.0);
-
-        let volume_24h = market.volume_24hr.unwrap_or(0.0);
-
-        // Parse token IDs
-        let token_id = market.clob_token_ids.as_ref()
-            .and_then(|ids| {
-                let parsed: Vec<String> = serde_json::from_str(ids).ok()?;
-                // If YES price is cheaper, buy YES (token 0); else buy NO (token 1)
-                if yes_price <= no_price {
-                    parsed.first().cloned()
-                } else {
-                    parsed.get(1).cloned()
-                }
-            })
-            .unwrap_or_default();
-
-        // Build URL
-        let event_slug = market.events.first()
-            .and_then(|e| e.slug.clone())
-            .unwrap_or_default();
-        let market_slug = market.slug.clone().unwrap_or_default();
-        let url = if !event_slug.is_empty() && !market_slug.is_empty() {
-            format!("https://polymarket.com/event/{}/{}", event_slug, market_slug)
-        } else if !event_slug.is_empty() {
-            format!("https://polymarket.com/event/{}", event_slug)
-        } else {
-            format!("https://polymarket.com/market/{}", market.id)
-        };
-
-        // Buy the cheaper side
-        let (buy_price, buy_side) = if yes_price <= no_price {
-            (yes_price, "YES")
-        } else {
-            (no_price, "NO")
-        };
-
-        // Score the opportunity (higher = better)
-        let score = self.score_opportunity(buy_price, liquidity, volume_24h, volume_total, &discovery_type);
-
-        Some(MarketOpportunity {
-            condition_id: market.condition_id.clone(),
-            token_id,
-            question: market.question.clone(),
-            slug: market_slug,
-            event_slug,
-            category: String::new(),
-            yes_price,
-            no_price,
-            liquidity,
-            volume_24h,
-            volume_total,
-            end_date: market.end_date_iso.clone(),
-            url,
-            discovery_type,
-            created_at: market.created_at.clone(),
-            score,
-            neg_risk: market.neg_risk,
-        })
+    /// Fetch markets, open or closed, for a batch of condition IDs in a single Gamma request
+    async fn fetch_markets_by_condition(&self, condition_ids: &[&str]) -> Result<Vec<GammaMarket>> {
+        let query = MarketQuery::new()
+            .condition_ids(condition_ids)
+            .limit(condition_ids.len() as u32);
+        self.gamma.fetch_markets(&query).await
     }
+}
 
-    /// Score an opportunity: higher score = better trade
-    fn score_opportunity(&self, price: f64, liquidity: f64, vol_24h: f64, vol_total: f64, discovery_type: &DiscoveryType) -> f64 {
-        let mut score = 0.0;
-
-        // Lower price = higher potential upside (max 40 pts)
-        if price <= 0.02 { score += 40.0; }
-        else if price <= 0.05 { score += 30.0; }
-        else if price <= 0.10 { score += 20.0; }
-        else if price <= 0.15 { score += 10.0; }
-
-        // Higher liquidity = safer trade (max 25 pts)
-        if liquidity >= 10000.0 { score += 25.0; }
-        else if liquidity >= 5000.0 { score += 20.0; }
-        else if liquidity >= 1000.0 { score += 15.0; }
-        else if liquidity >= 500.0 { score += 10.0; }
-
-        // Volume shows interest (max 20 pts)
-        if vol_24h >= 10000.0 { score += 20.0; }
-        else if vol_24h >= 1000.0 { score += 15.0; }
-        else if vol_24h >= 100.0 { score += 10.0; }
-
-        // Discovery type bonus (max 15 pts)
-        match discovery_type {
-            DiscoveryType::NewMarket => score += 15.0,
-            DiscoveryType::VolumeSurge => score += 12.0,
-            DiscoveryType::Mispriced => score += 10.0,
-            DiscoveryType::Longshot => score += 5.0,
-        }
-
-        score
-    }
-
-    /// Scan for volume spike opportunities (low-priced markets with sudden volume increase)
-    pub async fn scan_volume_spikes(&mut self, config: &Config) -> Result<Vec<MarketOpportunity>> {
-        info!("📈 Scanning for volume spikes...");
-        let mut opportunities = Vec::new();
-
-        let url = "https://gamma-api.polymarket.com/markets?limit=200&closed=false&order=volume24hr&ascending=false";
-
-        let response = self.client.get(url).send().await?;
-        if response.status() != 200 {
-            return Ok(opportunities);
-        }
-
-        let markets: Vec<GammaMarket> = response.json().await?;
-
-        for market in &markets {
-            if market.closed || market.resolved.unwrap_or(false) || !market.accepting_orders {
-                continue;
-            }
-
-            let vol_24h = market.volume_24hr.unwrap_or(0.0);
-            let prev_vol = self.volume_history.get(&market.id).copied().unwrap_or(0.0);
-
-            // Detect volume spike: current 24h vol is 3x+ previous recorded
-            let is_spike = prev_vol > 100.0 && vol_24h > prev_vol * 3.0;
-
-            // Update volume history
-            self.volume_history.insert(market.id.clone(), vol_24h);
-
-            if is_spike {
-                if let Some(mut opp) = self.evaluate_market(market, config, DiscoveryType::VolumeSurge) {
-                    let buy_price = opp.yes_price.min(opp.no_price);
-                    // Only consider low-priced markets with spikes (< 20¢)
-                    if buy_price <= 0.20 && opp.liquidity >= config.min_liquidity_usd {
-                        info!("📈 Volume spike detected: {} (vol {:.0} -> {:.0})", opp.question, prev_vol, vol_24h);
-                        opp.score += 15.0; // Bonus for volume spike
-                        opportunities.push(opp);
-                    }
-                }
-            }
-
-            self.known_market_ids.insert(market.id.clone());
-        }
-
-        info!("✅ Found {} volume spike opportunities", opportunities.len());
-        Ok(opportunities)
+/// Evaluate a market for opportunity potential, one opportunity per eligible outcome.
+/// Binary markets yield only their cheapest eligible outcome; markets with more than two
+/// outcomes yield every outcome priced at or below `max_price_cents`.
+pub fn evaluate_market(market: &GammaMarket, config: &Config, discovery_type: DiscoveryType) -> Vec<MarketOpportunity> {
+    let Some(outcome_prices) = market.parsed_prices() else { return Vec::new() };
+    if outcome_prices.len() < 2 {
+        return Vec::new();
     }
 
-    /// Scan for mispriced markets (YES + NO prices significantly != 1.0)
-    pub async fn scan_mispriced(&mut self, config: &Config) -> Result<Vec<MarketOpportunity>> {
-        info!("⚡ Scanning for mispriced markets...");
-        let mut opportunities = Vec::new();
-
-        let url = "https://gamma-api.polymarket.com/markets?limit=200&closed=false&order=volume24hr&ascending=false";
-
-        let response = self.client.get(url).send().await?;
-        if response.status() != 200 {
-            return Ok(opportunities);
-        }
-
-        let markets: Vec<GammaMarket> = response.json().await?;
-
-        for market in &markets {
-            if market.closed || market.resolved.unwrap_or(false) || !market.accepting_orders {
-                continue;
-            }
-
-            // Parse prices
-            let prices_str = match market.outcome_prices.as_ref() {
-                Some(p) => p,
-                None => continue,
-            };
-            let prices: Vec<String> = match serde_json::from_str(prices_str) {
-                Ok(p) => p,
-                Err(_) => continue,
-            };
-            if prices.len() < 2 {
-                continue;
-            }
-
-            let yes_price: f64 = prices[0].parse().unwrap_or(0.0);
-            let no_price: f64 = prices[1].parse().unwrap_or(0.0);
-
-            // Mispriced: YES + NO should be ~1.0 in an efficient market
-            // If sum < 0.95, there's an arbitrage / mispricing opportunity
-            let price_sum = yes_price + no_price;
-            if price_sum < 0.95 && price_sum > 0.0 {
-                let discount = 1.0 - price_sum;
-                if let Some(mut opp) = self.evaluate_market(market, config, DiscoveryType::Mispriced) {
-                    if opp.liquidity >= config.min_liquidity_usd {
-                        info!("⚡ Mispriced market: {} (YES {:.4} + NO {:.4} = {:.4}, discount {:.1}%)",
-                            opp.question, yes_price, no_price, price_sum, discount * 100.0);
-                        opp.score += discount * 100.0; // Bigger discount = higher score bonus
-                        opportunities.push(opp);
-                    }
-                }
-            }
-
-            self.known_market_ids.insert(market.id.clone());
-        }
-
-        info!("✅ Found {} mispriced opportunities", opportunities.len());
-        Ok(opportunities)
+    // Eligible outcomes, cheapest first. Skip zero-price or fully-priced outcomes.
+    let mut eligible: Vec<usize> = (0..outcome_prices.len())
+        .filter(|&i| {
+            let price = outcome_prices[i];
+            price > 0.001 && price < 0.99 && price <= config.max_price_decimal()
+        })
+        .collect();
+    eligible.sort_by(|&a, &b| outcome_prices[a].partial_cmp(&outcome_prices[b]).unwrap_or(std::cmp::Ordering::Equal));
+    if outcome_prices.len() == 2 {
+        eligible.truncate(1);
     }
 
-    /// Fetch current prices for tracked positions (by condition_id)
-    pub async fn fetch_current_prices(&self, condition_ids: &[String]) -> Result<Vec<(String, f64)>> {
-        if condition_ids.is_empty() {
-            return Ok(Vec::new());
-        }
+    eligible.into_iter()
+        .filter_map(|outcome_index| outcome_opportunity(market, outcome_index, config, discovery_type.clone()))
+        .collect()
+}
 
-        let mut price_updates = Vec::new();
-
-        // Fetch markets in batches
-        for chunk in condition_ids.chunks(20) {
-            for cid in chunk {
-                let url = format!(
-                    "https://gamma-api.polymarket.com/markets?condition_id={}&closed=false",
-                    cid
-                );
-
-                match self.client.get(&url).send().await {
-                    Ok(resp) => {
-                        if let Ok(markets) = resp.json::<Vec<GammaMarket>>().await {
-                            for market in &markets {
-                                if let Some(prices_str) = &market.outcome_prices {
-                                    if let Ok(prices) = serde_json::from_str::<Vec<String>>(prices_str) {
-                                        if prices.len() >= 2 {
-                                            let yes_price: f64 = prices[0].parse().unwrap_or(0.0);
-                                            let no_price: f64 = prices[1].parse().unwrap_or(0.0);
-                                            // Use the cheaper side (the one we would have bought)
-                                            let buy_price = yes_price.min(no_price);
-                                            price_updates.push((cid.clone(), buy_price));
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        debug!("Failed to fetch price for {}: {}", cid, e);
-                    }
-                }
-            }
+/// Build the opportunity for buying one outcome of a market at its Gamma price
+pub fn outcome_opportunity(market: &GammaMarket, outcome_index: usize, config: &Config, discovery_type: DiscoveryType) -> Option<MarketOpportunity> {
+    let outcome_prices = market.parsed_prices()?;
+    let price = *outcome_prices.get(outcome_index)?;
+    let outcomes = market.outcome_labels(outcome_prices.len());
+
+    let liquidity: f64 = market.liquidity.as_ref()
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(0.0);
+
+    let volume_total: f64 = market.volume.as_ref()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0.0);
+
+    let volume_24h = market.volume_24hr.unwrap_or(0.0);
+
+    // Build URL
+    let event_slug = market.events.first()
+        .and_then(|e| e.slug.clone())
+        .unwrap_or_default();
+    let market_slug = market.slug.clone().unwrap_or_default();
+    let url = if !event_slug.is_empty() && !market_slug.is_empty() {
+        format!("https://polymarket.com/event/{}/{}", event_slug, market_slug)
+    } else if !event_slug.is_empty() {
+        format!("https://polymarket.com/event/{}", event_slug)
+    } else {
+        format!("https://polymarket.com/market/{}", market.id)
+    };
+
+    // Capital lockup until resolution
+    let end_time = market.end_time();
+    let days_to_resolution = end_time
+        .map(|end| end.signed_duration_since(Utc::now()).num_seconds() as f64 / 86_400.0);
+    let annualized_upside = days_to_resolution
+        .filter(|days| *days > 0.0)
+        .map(|days| annualized_upside(price, days));
+
+    // Score the opportunity (higher = better)
+    let score_breakdown = scoring::score(&ScoreInputs {
+        price,
+        liquidity,
+        volume_24h,
+        annualized_upside,
+        discovery_type: &discovery_type,
+    }, &config.scoring);
+
+    Some(MarketOpportunity {
+        condition_id: market.condition_id.clone(),
+        token_id: market.token_ids().get(outcome_index).cloned().unwrap_or_default(),
+        question: market.question.clone(),
+        slug: market_slug,
+        event_slug,
+        category: market.primary_category(),
+        tags: market.tag_names(),
+        outcome: outcomes[outcome_index].clone(),
+        outcome_index,
+        price,
+        outcomes,
+        outcome_prices,
+        liquidity,
+        volume_24h,
+        volume_total,
+        end_date: end_time.map(|t| t.to_rfc3339()).or_else(|| market.end_date_iso.clone()),
+        days_to_resolution,
+        annualized_upside,
+        url,
+        reasons: vec![discovery_type.clone()],
+        discovery_type,
+        created_at: market.created_at.clone(),
+        score: score_breakdown.total(),
+        score_breakdown,
+        neg_risk: market.neg_risk,
+        legs: Vec::new(),
+        guaranteed_payout: None,
+        max_shares: None,
+        predicted_probability: None,
+        expected_value: None,
+    })
+}
 
-            // Small delay between batches to avoid rate limiting
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        }
+/// Winning payout multiple minus one, divided by the years of lockup (at least one day)
+pub fn annualized_upside(price: f64, days_to_resolution: f64) -> f64 {
+    let upside = 1.0 / price - 1.0;
+    upside / (days_to_resolution.max(1.0) / 365.0)
+}
 
-        Ok(price_updates)
-    }
+/// Price of the outcome a position holds, located by token ID (falling back to its outcome index)
+fn held_outcome_price(market: &GammaMarket, pos: &Position) -> Option<f64> {
+    let prices = market.parsed_prices()?;
+    prices.get(held_outcome_index(market, pos)).copied()
+}
 
-    /// Check if a full longshot scan is needed
-    pub fn needs_full_scan(&self, config: &Config) -> bool {
-        match self.last_full_scan {
-            None => true,
-            Some(last) => {
-                let elapsed = Utc::now().signed_duration_since(last).num_seconds() as u64;
-                elapsed >= config.longshot_scan_interval_secs
-            }
-        }
-    }
+fn held_outcome_index(market: &GammaMarket, pos: &Position) -> usize {
+    market.token_ids().iter()
+        .position(|t| *t == pos.token_id)
+        .unwrap_or(pos.outcome_index)
 }
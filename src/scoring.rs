@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::scanner::DiscoveryType;
+
+/// Points awarded once a value reaches `at`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Breakpoint {
+    pub at: f64,
+    pub points: f64,
+}
+
+/// A tiered factor: the first listed breakpoint the value reaches wins, so list the best tier first
+#[derive(Debug, Clone, Deserialize)]
+pub struct TieredFactor {
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Breakpoints are upper bounds (value <= at) instead of lower bounds (value >= at)
+    #[serde(default)]
+    pub lower_is_better: bool,
+    pub breakpoints: Vec<Breakpoint>,
+    /// Points when no breakpoint is reached
+    #[serde(default)]
+    pub otherwise: f64,
+}
+
+impl TieredFactor {
+    fn new(lower_is_better: bool, breakpoints: &[(f64, f64)], otherwise: f64) -> Self {
+        Self {
+            weight: 1.0,
+            lower_is_better,
+            breakpoints: breakpoints.iter().map(|&(at, points)| Breakpoint { at, points }).collect(),
+            otherwise,
+        }
+    }
+
+    fn points(&self, value: f64) -> f64 {
+        let tier = self.breakpoints.iter().find(|b| {
+            if self.lower_is_better { value <= b.at } else { value >= b.at }
+        });
+        tier.map_or(self.otherwise, |b| b.points) * self.weight
+    }
+}
+
+/// Factors, weights and breakpoints of the opportunity score (higher = better)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScoringConfig {
+    /// Lower price = higher potential upside
+    #[serde(default = "default_price")]
+    pub price: TieredFactor,
+
+    /// Higher liquidity = safer trade
+    #[serde(default = "default_liquidity")]
+    pub liquidity: TieredFactor,
+
+    /// Volume shows interest
+    #[serde(default = "default_volume_24h")]
+    pub volume_24h: TieredFactor,
+
+    /// Capital lockup: annualized upside rewards near-term resolution (skipped without an end date)
+    #[serde(default = "default_annualized_upside")]
+    pub annualized_upside: TieredFactor,
+
+    /// Bonus per discovery type (new_market, longshot, volume_surge, binary_arbitrage, event_arbitrage)
+    #[serde(default = "default_discovery_bonus")]
+    pub discovery_bonus: HashMap<String, f64>,
+
+    /// Extra points for a confirmed volume spike
+    #[serde(default = "default_volume_spike_bonus")]
+    pub volume_spike_bonus: f64,
+
+    /// Points per percentage point of locked-in arbitrage edge
+    #[serde(default = "default_weight")]
+    pub arbitrage_edge_per_pct: f64,
+
+    /// Points for each additional source that found the same market
+    #[serde(default = "default_extra_source_bonus")]
+    pub extra_source_bonus: f64,
+}
+
+fn default_weight() -> f64 { 1.0 }
+fn default_price() -> TieredFactor {
+    TieredFactor::new(true, &[(0.02, 40.0), (0.05, 30.0), (0.10, 20.0), (0.15, 10.0)], 0.0)
+}
+fn default_liquidity() -> TieredFactor {
+    TieredFactor::new(false, &[(10000.0, 25.0), (5000.0, 20.0), (1000.0, 15.0), (500.0, 10.0)], 0.0)
+}
+fn default_volume_24h() -> TieredFactor {
+    TieredFactor::new(false, &[(10000.0, 20.0), (1000.0, 15.0), (100.0, 10.0)], 0.0)
+}
+fn default_annualized_upside() -> TieredFactor {
+    TieredFactor::new(false, &[(1000.0, 15.0), (200.0, 10.0), (50.0, 5.0), (10.0, 0.0)], -10.0)
+}
+fn default_discovery_bonus() -> HashMap<String, f64> {
+    [
+        ("new_market", 15.0),
+        ("volume_surge", 12.0),
+        ("binary_arbitrage", 10.0),
+        ("event_arbitrage", 10.0),
+        ("longshot", 5.0),
+    ].into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+}
+fn default_volume_spike_bonus() -> f64 { 15.0 }
+fn default_extra_source_bonus() -> f64 { 5.0 }
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            price: default_price(),
+            liquidity: default_liquidity(),
+            volume_24h: default_volume_24h(),
+            annualized_upside: default_annualized_upside(),
+            discovery_bonus: default_discovery_bonus(),
+            volume_spike_bonus: default_volume_spike_bonus(),
+            arbitrage_edge_per_pct: default_weight(),
+            extra_source_bonus: default_extra_source_bonus(),
+        }
+    }
+}
+
+/// Points contributed by one factor
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreComponent {
+    pub factor: String,
+    pub points: f64,
+}
+
+/// Per-factor breakdown of an opportunity's score
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScoreBreakdown {
+    pub components: Vec<ScoreComponent>,
+}
+
+impl ScoreBreakdown {
+    pub fn add(&mut self, factor: &str, points: f64) {
+        self.components.push(ScoreComponent { factor: factor.to_string(), points });
+    }
+
+    pub fn total(&self) -> f64 {
+        self.components.iter().map(|c| c.points).sum()
+    }
+
+    /// Non-zero components, e.g. "price +30 · liquidity +15 · lockup -10"
+    pub fn summary(&self) -> String {
+        self.components.iter()
+            .filter(|c| c.points != 0.0)
+            .map(|c| format!("{} {:+.0}", c.factor, c.points))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// Market features the base score is computed from
+pub struct ScoreInputs<'a> {
+    pub price: f64,
+    pub liquidity: f64,
+    pub volume_24h: f64,
+    pub annualized_upside: Option<f64>,
+    pub discovery_type: &'a DiscoveryType,
+}
+
+/// Score an opportunity's base factors
+pub fn score(inputs: &ScoreInputs, config: &ScoringConfig) -> ScoreBreakdown {
+    let mut breakdown = ScoreBreakdown::default();
+    breakdown.add("price", config.price.points(inputs.price));
+    breakdown.add("liquidity", config.liquidity.points(inputs.liquidity));
+    breakdown.add("volume", config.volume_24h.points(inputs.volume_24h));
+    if let Some(annualized) = inputs.annualized_upside {
+        breakdown.add("lockup", config.annualized_upside.points(annualized));
+    }
+    let bonus = config.discovery_bonus.get(inputs.discovery_type.key()).copied().unwrap_or(0.0);
+    breakdown.add(inputs.discovery_type.key(), bonus);
+    breakdown
+}
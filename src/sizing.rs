@@ -0,0 +1,107 @@
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::scanner::MarketOpportunity;
+
+/// How the stake for each trade is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMethod {
+    /// Always `max_per_trade_usd`
+    Fixed,
+    /// A fixed fraction of current equity
+    PercentOfEquity,
+    /// Fractional Kelly on the estimated edge at the entry price
+    Kelly,
+}
+
+/// Stake sizing settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct SizingConfig {
+    #[serde(default = "default_method")]
+    pub method: SizingMethod,
+
+    /// Starting capital; equity is this plus unrealized P/L
+    #[serde(default = "default_bankroll")]
+    pub bankroll_usd: f64,
+
+    /// Fraction of equity staked per trade (percent_of_equity)
+    #[serde(default = "default_equity_fraction")]
+    pub equity_fraction: f64,
+
+    /// Multiplier on the full Kelly stake (kelly)
+    #[serde(default = "default_kelly_fraction")]
+    pub kelly_fraction: f64,
+
+    /// Stakes below this are skipped
+    #[serde(default = "default_min_stake")]
+    pub min_stake_usd: f64,
+}
+
+fn default_method() -> SizingMethod { SizingMethod::Fixed }
+fn default_bankroll() -> f64 { 1000.0 }
+fn default_equity_fraction() -> f64 { 0.01 }
+fn default_kelly_fraction() -> f64 { 0.25 }
+fn default_min_stake() -> f64 { 1.0 }
+
+impl Default for SizingConfig {
+    fn default() -> Self {
+        Self {
+            method: default_method(),
+            bankroll_usd: default_bankroll(),
+            equity_fraction: default_equity_fraction(),
+            kelly_fraction: default_kelly_fraction(),
+            min_stake_usd: default_min_stake(),
+        }
+    }
+}
+
+/// Current capital available for sizing
+pub struct Bankroll {
+    pub equity: f64,
+    /// Capital already committed to positions and open orders
+    pub exposure: f64,
+}
+
+/// Win probability used for Kelly: the model's prediction, if any. Baskets pay out with certainty.
+fn win_probability(opp: &MarketOpportunity) -> Option<f64> {
+    if opp.is_basket() {
+        return Some(1.0);
+    }
+    opp.predicted_probability
+}
+
+/// Full Kelly fraction of equity for buying at `price` a contract paying `payout`
+fn kelly(probability: f64, price: f64, payout: f64) -> f64 {
+    // Net odds per dollar staked
+    let odds = payout / price - 1.0;
+    if odds <= 0.0 {
+        return 0.0;
+    }
+    ((probability * odds - (1.0 - probability)) / odds).max(0.0)
+}
+
+/// Stake in USDC for an opportunity, capped at `max_per_trade_usd` and (for equity-based
+/// methods) uncommitted equity. Returns 0 when the stake is below `min_stake_usd`.
+pub fn stake(opp: &MarketOpportunity, config: &Config, bankroll: &Bankroll) -> f64 {
+    let sizing = &config.sizing;
+    let equity = bankroll.equity.max(0.0);
+
+    let raw = match sizing.method {
+        SizingMethod::Fixed => config.max_per_trade_usd,
+        SizingMethod::PercentOfEquity => equity * sizing.equity_fraction,
+        SizingMethod::Kelly => {
+            // Without a model prediction there is no estimated edge to size on
+            let payout = opp.guaranteed_payout.unwrap_or(1.0);
+            let fraction = win_probability(opp).map_or(0.0, |p| kelly(p, opp.price, payout));
+            equity * fraction * sizing.kelly_fraction
+        }
+    };
+
+    let available = match sizing.method {
+        SizingMethod::Fixed => f64::INFINITY,
+        _ => (equity - bankroll.exposure).max(0.0),
+    };
+    let stake = raw.min(config.max_per_trade_usd).min(available);
+    if stake < sizing.min_stake_usd { 0.0 } else { stake }
+}
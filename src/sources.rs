@@ -0,0 +1,632 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use std::collections::HashSet;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::gamma::{GammaEvent, GammaMarket, MarketSnapshot};
+use crate::orderbook::{match_basket_depth, match_pair_depth, BookLevel, OrderBook, OrderBookClient};
+use crate::scanner::{evaluate_market, outcome_opportunity, DiscoveryType, MarketOpportunity, OpportunityLeg};
+use crate::scoring::{self, ScoreBreakdown, ScoreInputs};
+use crate::volume_history::{Sample, SpikeParams, VolumeHistory};
+
+/// Highest-volume markets examined by the spike and mispricing scans
+const HEAD_MARKETS: usize = 200;
+/// Default seconds between event arbitrage scans, which page through `/events`
+const EVENT_ARB_INTERVAL_SECS: u64 = 120;
+
+/// A signal that turns the shared market snapshot into trade opportunities.
+///
+/// Implementations are registered in [`SourceRegistry::new`] and enabled/scheduled
+/// per name through `Config.sources`.
+pub trait OpportunitySource: Send {
+    /// Stable name used as the key in `Config.sources`
+    fn name(&self) -> &'static str;
+
+    /// Seconds between runs when not overridden in config (0 = every cycle)
+    fn default_interval_secs(&self, config: &Config) -> u64;
+
+    /// Whether this source needs the full paged universe rather than the snapshot head
+    fn needs_full_snapshot(&self) -> bool {
+        false
+    }
+
+    /// Whether this source needs events with their member markets in the snapshot
+    fn needs_events(&self) -> bool {
+        false
+    }
+
+    /// Scan the snapshot for opportunities
+    fn scan<'a>(&'a mut self, snapshot: &'a MarketSnapshot, config: &'a Config) -> BoxFuture<'a, Result<Vec<MarketOpportunity>>>;
+}
+
+struct ScheduledSource {
+    source: Box<dyn OpportunitySource>,
+    last_run: Option<DateTime<Utc>>,
+}
+
+/// Runs registered opportunity sources on their configured schedules
+pub struct SourceRegistry {
+    sources: Vec<ScheduledSource>,
+}
+
+impl SourceRegistry {
+    /// Registry with all built-in sources
+    pub fn new(config: &Config) -> Self {
+        let mut registry = Self { sources: Vec::new() };
+        registry.register(Box::new(NewMarketSource::default()));
+        registry.register(Box::new(LongshotSource));
+        registry.register(Box::new(VolumeSpikeSource::new(config)));
+        registry.register(Box::new(MispricedSource::new()));
+        registry.register(Box::new(EventArbitrageSource::new()));
+        registry
+    }
+
+    pub fn register(&mut self, source: Box<dyn OpportunitySource>) {
+        self.sources.push(ScheduledSource { source, last_run: None });
+    }
+
+    /// Indices of sources that are enabled and due to run
+    pub fn due(&self, config: &Config) -> Vec<usize> {
+        let now = Utc::now();
+        self.sources.iter().enumerate()
+            .filter(|(_, s)| {
+                let settings = config.sources.get(s.source.name());
+                if settings.is_some_and(|c| !c.enabled) {
+                    return false;
+                }
+                let interval = settings
+                    .and_then(|c| c.interval_secs)
+                    .unwrap_or_else(|| s.source.default_interval_secs(config));
+                match s.last_run {
+                    None => true,
+                    Some(last) => now.signed_duration_since(last).num_seconds() as u64 >= interval,
+                }
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Whether any of the given sources needs the full snapshot
+    pub fn needs_full_snapshot(&self, due: &[usize]) -> bool {
+        due.iter().any(|&i| self.sources[i].source.needs_full_snapshot())
+    }
+
+    /// Whether any of the given sources needs events in the snapshot
+    pub fn needs_events(&self, due: &[usize]) -> bool {
+        due.iter().any(|&i| self.sources[i].source.needs_events())
+    }
+
+    /// Run the given sources against the snapshot, collecting their opportunities
+    pub async fn run(&mut self, due: &[usize], snapshot: &MarketSnapshot, config: &Config) -> Vec<MarketOpportunity> {
+        let mut opportunities = Vec::new();
+
+        for &i in due {
+            let scheduled = &mut self.sources[i];
+            match scheduled.source.scan(snapshot, config).await {
+                Ok(opps) => opportunities.extend(opps),
+                Err(e) => warn!("{} scan error: {}", scheduled.source.name(), e),
+            }
+            scheduled.last_run = Some(Utc::now());
+        }
+
+        opportunities
+    }
+}
+
+/// Newly listed markets (and cheap outcomes among the most recent listings)
+#[derive(Default)]
+pub struct NewMarketSource {
+    known_market_ids: HashSet<String>,
+}
+
+impl OpportunitySource for NewMarketSource {
+    fn name(&self) -> &'static str {
+        "new_markets"
+    }
+
+    fn default_interval_secs(&self, _config: &Config) -> u64 {
+        0
+    }
+
+    fn scan<'a>(&'a mut self, snapshot: &'a MarketSnapshot, config: &'a Config) -> BoxFuture<'a, Result<Vec<MarketOpportunity>>> {
+        Box::pin(async move {
+            info!("🔍 Scanning for new markets...");
+            let mut opportunities = Vec::new();
+
+            for market in &snapshot.recent {
+                if !market.is_tradeable() {
+                    continue;
+                }
+
+                // Check if this is a new market we haven't seen
+                let is_new = !self.known_market_ids.contains(&market.id);
+
+                let discovery_type = if is_new { DiscoveryType::NewMarket } else { DiscoveryType::Longshot };
+                opportunities.extend(evaluate_market(market, config, discovery_type));
+
+                self.known_market_ids.insert(market.id.clone());
+            }
+
+            // Markets already trading in the volume-ranked universe are not new listings
+            self.known_market_ids.extend(snapshot.by_volume.iter().map(|m| m.id.clone()));
+
+            info!("✅ Found {} opportunities from new market scan", opportunities.len());
+            Ok(opportunities)
+        })
+    }
+}
+
+/// Full scan for longshot markets (low-price high-upside)
+pub struct LongshotSource;
+
+impl OpportunitySource for LongshotSource {
+    fn name(&self) -> &'static str {
+        "longshots"
+    }
+
+    fn default_interval_secs(&self, config: &Config) -> u64 {
+        config.longshot_scan_interval_secs
+    }
+
+    fn needs_full_snapshot(&self) -> bool {
+        true
+    }
+
+    fn scan<'a>(&'a mut self, snapshot: &'a MarketSnapshot, config: &'a Config) -> BoxFuture<'a, Result<Vec<MarketOpportunity>>> {
+        Box::pin(async move {
+            info!("🎯 Scanning for longshot markets...");
+            let mut opportunities = Vec::new();
+
+            for market in &snapshot.by_volume {
+                if !market.is_tradeable() {
+                    continue;
+                }
+
+                opportunities.extend(
+                    evaluate_market(market, config, DiscoveryType::Longshot)
+                        .into_iter()
+                        .filter(|opp| opp.liquidity >= config.min_liquidity_usd)
+                );
+            }
+
+            info!("✅ Found {} longshot opportunities", opportunities.len());
+            Ok(opportunities)
+        })
+    }
+}
+
+/// Low-priced markets whose traded volume rate jumps well above its rolling baseline
+pub struct VolumeSpikeSource {
+    history: VolumeHistory,
+}
+
+impl VolumeSpikeSource {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            history: VolumeHistory::new(config.spike_history_path.as_deref()),
+        }
+    }
+}
+
+impl OpportunitySource for VolumeSpikeSource {
+    fn name(&self) -> &'static str {
+        "volume_spikes"
+    }
+
+    fn default_interval_secs(&self, _config: &Config) -> u64 {
+        0
+    }
+
+    fn scan<'a>(&'a mut self, snapshot: &'a MarketSnapshot, config: &'a Config) -> BoxFuture<'a, Result<Vec<MarketOpportunity>>> {
+        Box::pin(async move {
+            info!("📈 Scanning for volume spikes...");
+            let mut opportunities = Vec::new();
+            let params = SpikeParams {
+                window_secs: config.spike_window_secs as i64,
+                halflife_secs: config.spike_halflife_secs.max(1) as f64,
+            };
+            self.history.set_path(config.spike_history_path.as_deref());
+
+            for market in snapshot.top_by_volume(HEAD_MARKETS) {
+                if !market.is_tradeable() {
+                    continue;
+                }
+
+                let sample = Sample {
+                    timestamp: snapshot.fetched_at,
+                    volume_24h: market.volume_24hr.unwrap_or(0.0),
+                    volume_total: market.volume.as_ref().and_then(|v| v.parse().ok()).unwrap_or(0.0),
+                    price: market.parsed_prices()
+                        .and_then(|p| p.into_iter().reduce(f64::min))
+                        .unwrap_or(0.0),
+                };
+
+                let Some(reading) = self.history.observe(&market.id, sample, &params) else { continue };
+                let is_spike = reading.observations >= config.spike_min_samples
+                    && reading.zscore >= config.spike_z_threshold;
+
+                if is_spike {
+                    for mut opp in evaluate_market(market, config, DiscoveryType::VolumeSurge) {
+                        // Only consider low-priced markets with spikes (< 20¢)
+                        if opp.price <= 0.20 && opp.liquidity >= config.min_liquidity_usd {
+                            info!("📈 Volume spike detected: {} (${:.0}/min vs baseline ${:.0}/min, z={:.1})",
+                                opp.question, reading.rate, reading.baseline, reading.zscore);
+                            opp.add_score("spike", config.scoring.volume_spike_bonus);
+                            opportunities.push(opp);
+                        }
+                    }
+                }
+            }
+
+            self.history.prune(params.window_secs);
+            if let Err(e) = self.history.save() {
+                warn!("Failed to persist volume history: {}", e);
+            }
+
+            info!("✅ Found {} volume spike opportunities", opportunities.len());
+            Ok(opportunities)
+        })
+    }
+}
+
+/// Binary markets whose YES + NO asks sum below the $1 payout: buys both legs in matched
+/// size against the order book, locking in the discount at resolution
+pub struct MispricedSource {
+    books: OrderBookClient,
+}
+
+impl MispricedSource {
+    pub fn new() -> Self {
+        Self { books: OrderBookClient::new() }
+    }
+}
+
+impl OpportunitySource for MispricedSource {
+    fn name(&self) -> &'static str {
+        "mispriced"
+    }
+
+    fn default_interval_secs(&self, config: &Config) -> u64 {
+        config.longshot_scan_interval_secs
+    }
+
+    fn scan<'a>(&'a mut self, snapshot: &'a MarketSnapshot, config: &'a Config) -> BoxFuture<'a, Result<Vec<MarketOpportunity>>> {
+        Box::pin(async move {
+            info!("⚡ Scanning for mispriced markets...");
+
+            // Binary markets only
+            let markets: Vec<&GammaMarket> = snapshot.top_by_volume(HEAD_MARKETS).iter()
+                .filter(|m| m.is_tradeable())
+                .filter(|m| m.parsed_prices().is_some_and(|p| p.len() == 2) && m.token_ids().len() == 2)
+                .collect();
+            let token_ids: Vec<String> = markets.iter().flat_map(|m| m.token_ids()).collect();
+            let books = self.books.fetch_many(&token_ids).await?;
+
+            let mut opportunities = Vec::new();
+            for market in markets {
+                let token_ids = market.token_ids();
+                let (Some(yes_book), Some(no_book)) = (books.get(&token_ids[0]), books.get(&token_ids[1])) else {
+                    continue;
+                };
+
+                // Mispriced: the best YES and NO asks, each from its own book, sum below the payout
+                let (Some(yes_ask), Some(no_ask)) = (yes_book.asks.first(), no_book.asks.first()) else { continue };
+                if yes_ask.price + no_ask.price > max_pair_price(config) {
+                    continue;
+                }
+
+                match pair_opportunity(market, yes_book, no_book, config) {
+                    Some(opp) => opportunities.push(opp),
+                    None => debug!("Mispriced market {} has no executable pair depth", market.question),
+                }
+            }
+
+            info!("✅ Found {} mispriced opportunities", opportunities.len());
+            Ok(opportunities)
+        })
+    }
+}
+
+/// Size a YES + NO pair against both order books, if it clears `binary_arb_min_edge` after fees
+fn pair_opportunity(market: &GammaMarket, yes_book: &OrderBook, no_book: &OrderBook, config: &Config) -> Option<MarketOpportunity> {
+    let token_ids = market.token_ids();
+    if token_ids.len() != 2 {
+        return None;
+    }
+
+    let fee_multiplier = 1.0 + config.arbitrage_fee_bps / 10_000.0;
+    let fill = match_pair_depth(&yes_book.asks, &no_book.asks, max_pair_price(config))?;
+    let mut opp = outcome_opportunity(market, 0, config, DiscoveryType::BinaryArbitrage)?;
+
+    let average_pair_price = fill.cost / fill.shares;
+    let edge = 1.0 / (average_pair_price * fee_multiplier) - 1.0;
+    let outcomes = market.outcome_labels(2);
+    let (yes_limit, no_limit) = fill.worst_prices;
+
+    info!("⚡ Binary arbitrage: {} ({} {:.4} + {} {:.4} asks, {:.0} pairs @ ${:.4}, edge {:.1}%)",
+        market.question, outcomes[0], yes_limit, outcomes[1], no_limit, fill.shares, average_pair_price, edge * 100.0);
+
+    // Limit each leg at the worst level reached so the order sweeps the matched depth
+    opp.legs = [(0, yes_limit), (1, no_limit)].into_iter()
+        .map(|(index, price)| OpportunityLeg {
+            condition_id: market.condition_id.clone(),
+            token_id: token_ids[index].clone(),
+            question: market.question.clone(),
+            outcome: outcomes[index].clone(),
+            outcome_index: index,
+            price,
+            neg_risk: market.neg_risk,
+        })
+        .collect();
+    opp.outcome = "Both".to_string();
+    opp.token_id = String::new();
+    opp.price = yes_limit + no_limit;
+    opp.guaranteed_payout = Some(1.0);
+    opp.max_shares = Some(fill.shares);
+    opp.annualized_upside = None;
+    opp.set_score(arbitrage_score(opp.price, opp.liquidity, opp.volume_24h, &opp.discovery_type, edge, config));
+
+    Some(opp)
+}
+
+/// Neg-risk events where buying one share of every YES (or every NO) outcome costs less
+/// than the basket's guaranteed payout after fees, sized against every leg's order book
+pub struct EventArbitrageSource {
+    books: OrderBookClient,
+}
+
+impl EventArbitrageSource {
+    pub fn new() -> Self {
+        Self { books: OrderBookClient::new() }
+    }
+}
+
+impl OpportunitySource for EventArbitrageSource {
+    fn name(&self) -> &'static str {
+        "event_arbitrage"
+    }
+
+    fn default_interval_secs(&self, _config: &Config) -> u64 {
+        EVENT_ARB_INTERVAL_SECS
+    }
+
+    fn needs_events(&self) -> bool {
+        true
+    }
+
+    fn scan<'a>(&'a mut self, snapshot: &'a MarketSnapshot, config: &'a Config) -> BoxFuture<'a, Result<Vec<MarketOpportunity>>> {
+        Box::pin(async move {
+            info!("🧺 Scanning neg-risk events for basket arbitrage...");
+            let mut opportunities = Vec::new();
+
+            for event in &snapshot.events {
+                // Augmented events and "Other" members don't name every outcome, so no basket is complete
+                if !event.neg_risk || event.closed || event.neg_risk_augmented {
+                    continue;
+                }
+                if event.markets.iter().any(is_placeholder_member) {
+                    continue;
+                }
+
+                // Closed members must have resolved NO; every open member must be buyable
+                let resolved_no = |m: &GammaMarket| m.parsed_prices().and_then(|p| p.first().copied()).is_some_and(|p| p <= 0.01);
+                if !event.markets.iter().filter(|m| m.closed).all(resolved_no) {
+                    continue;
+                }
+                let open: Vec<&GammaMarket> = event.markets.iter().filter(|m| !m.closed).collect();
+                if open.len() < 2 || open.len() > config.event_arb_max_legs {
+                    continue;
+                }
+                if open.iter().any(|m| !m.accepting_orders) {
+                    continue;
+                }
+
+                // Exactly one member resolves YES: a YES basket pays 1, a NO basket pays N - 1.
+                // Gamma's top of book shortlists the basket; the legs' order books size it.
+                let yes_asks: Option<Vec<f64>> = open.iter()
+                    .map(|m| m.best_ask.filter(|a| *a > 0.0 && *a < 1.0))
+                    .collect();
+                let no_asks: Option<Vec<f64>> = open.iter()
+                    .map(|m| m.best_bid.filter(|b| *b > 0.0 && *b < 1.0).map(|b| 1.0 - b))
+                    .collect();
+                let baskets = [(0, yes_asks, 1.0), (1, no_asks, (open.len() - 1) as f64)];
+
+                for (outcome_index, asks, payout) in baskets {
+                    let Some(asks) = asks else { continue };
+                    if asks.iter().sum::<f64>() > max_basket_price(payout, config) {
+                        continue;
+                    }
+                    let Some(token_ids) = open.iter().map(|m| m.token_ids().get(outcome_index).cloned()).collect::<Option<Vec<_>>>() else {
+                        continue;
+                    };
+                    let books = match futures::future::try_join_all(token_ids.iter().map(|t| self.books.fetch(t))).await {
+                        Ok(books) => books,
+                        Err(e) => {
+                            warn!("Failed to fetch basket books for {}: {}", event.title.as_deref().unwrap_or(&event.id), e);
+                            continue;
+                        }
+                    };
+                    opportunities.extend(basket_opportunity(event, &open, outcome_index, &books, payout, config));
+                }
+            }
+
+            info!("✅ Found {} event arbitrage opportunities", opportunities.len());
+            Ok(opportunities)
+        })
+    }
+}
+
+/// Highest YES + NO pair price that still earns `binary_arb_min_edge` on the $1 payout after fees
+fn max_pair_price(config: &Config) -> f64 {
+    1.0 / ((1.0 + config.binary_arb_min_edge) * (1.0 + config.arbitrage_fee_bps / 10_000.0))
+}
+
+/// Highest basket price that still earns `event_arb_min_edge` on `payout` after fees
+fn max_basket_price(payout: f64, config: &Config) -> f64 {
+    payout / ((1.0 + config.event_arb_min_edge) * (1.0 + config.arbitrage_fee_bps / 10_000.0))
+}
+
+/// Catch-all or unnamed member of an event, whose outcome set is still open
+fn is_placeholder_member(market: &GammaMarket) -> bool {
+    let label = market.group_item_title.as_deref().unwrap_or(&market.question).trim();
+    label.is_empty() || label.eq_ignore_ascii_case("other") || label.to_lowercase().starts_with("other ")
+}
+
+/// Build a basket opportunity buying outcome `outcome_index` of every market, sized to the
+/// depth all legs' `books` offer while the edge over `payout` clears `event_arb_min_edge` after fees
+fn basket_opportunity(
+    event: &GammaEvent,
+    markets: &[&GammaMarket],
+    outcome_index: usize,
+    books: &[OrderBook],
+    payout: f64,
+    config: &Config,
+) -> Option<MarketOpportunity> {
+    let ladders: Vec<&[BookLevel]> = books.iter().map(|b| b.asks.as_slice()).collect();
+    let fill = match_basket_depth(&ladders, max_basket_price(payout, config))?;
+    if fill.cost < config.sizing.min_stake_usd {
+        debug!("Skipping basket {} - only ${:.2} executable across every leg", event.id, fill.cost);
+        return None;
+    }
+
+    let cost = fill.cost / fill.shares;
+    let edge = payout / (cost * (1.0 + config.arbitrage_fee_bps / 10_000.0)) - 1.0;
+
+    // Limit each leg at the worst level reached so the orders sweep the matched depth
+    let asks = fill.worst_prices;
+    let legs: Vec<OpportunityLeg> = markets.iter().zip(&asks)
+        .map(|(market, &price)| {
+            Some(OpportunityLeg {
+                condition_id: market.condition_id.clone(),
+                token_id: market.token_ids().get(outcome_index)?.clone(),
+                question: market.question.clone(),
+                outcome: market.outcome_labels(2)[outcome_index].clone(),
+                outcome_index,
+                price,
+                neg_risk: market.neg_risk,
+            })
+        })
+        .collect::<Option<_>>()?;
+
+    let liquidity = markets.iter()
+        .map(|m| m.liquidity.as_ref().and_then(|l| l.parse().ok()).unwrap_or(0.0))
+        .fold(f64::INFINITY, f64::min);
+    let volume_24h: f64 = markets.iter().map(|m| m.volume_24hr.unwrap_or(0.0)).sum();
+    let volume_total: f64 = markets.iter()
+        .map(|m| m.volume.as_ref().and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0))
+        .sum();
+    let end_time = markets.iter().filter_map(|m| m.end_time()).max();
+    let days_to_resolution = end_time
+        .map(|end| end.signed_duration_since(Utc::now()).num_seconds() as f64 / 86_400.0);
+
+    let title = event.title.clone().unwrap_or_else(|| markets[0].question.clone());
+    let side = if outcome_index == 0 { "YES" } else { "NO" };
+    let event_slug = event.slug.clone().unwrap_or_default();
+
+    info!("🧺 Event arbitrage: {} - {} legs of {} cost ${:.4} for ${:.0} payout, {:.0} baskets deep (edge {:.1}%)",
+        title, legs.len(), side, cost, payout, fill.shares, edge * 100.0);
+
+    let discovery_type = DiscoveryType::EventArbitrage;
+    let score_breakdown = arbitrage_score(cost / payout, liquidity, volume_24h, &discovery_type, edge, config);
+
+    Some(MarketOpportunity {
+        condition_id: format!("event:{}", event.id),
+        token_id: String::new(),
+        question: format!("{} (all {})", title, side),
+        slug: String::new(),
+        url: format!("https://polymarket.com/event/{}", event_slug),
+        event_slug,
+        category: markets[0].primary_category(),
+        tags: markets[0].tag_names(),
+        outcome: format!("All {}", side),
+        outcome_index,
+        price: asks.iter().sum(),
+        outcomes: legs.iter().map(|l| l.question.clone()).collect(),
+        outcome_prices: asks,
+        liquidity,
+        volume_24h,
+        volume_total,
+        end_date: end_time.map(|t| t.to_rfc3339()),
+        days_to_resolution,
+        annualized_upside: None,
+        reasons: vec![discovery_type.clone()],
+        discovery_type,
+        created_at: None,
+        score: score_breakdown.total(),
+        score_breakdown,
+        neg_risk: true,
+        legs,
+        guaranteed_payout: Some(payout),
+        max_shares: Some(fill.shares),
+        predicted_probability: None,
+        expected_value: None,
+    })
+}
+
+/// Base score of a basket priced at `price` per $1 of payout, plus points for its edge
+fn arbitrage_score(price: f64, liquidity: f64, volume_24h: f64, discovery_type: &DiscoveryType, edge: f64, config: &Config) -> ScoreBreakdown {
+    let mut breakdown = scoring::score(&ScoreInputs {
+        price,
+        liquidity,
+        volume_24h,
+        annualized_upside: None,
+        discovery_type,
+    }, &config.scoring);
+    breakdown.add("edge", edge * 100.0 * config.scoring.arbitrage_edge_per_pct);
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(asks: &[(f64, f64)]) -> OrderBook {
+        OrderBook {
+            bids: Vec::new(),
+            asks: asks.iter().map(|&(price, size)| BookLevel { price, size }).collect(),
+            tick_size: 0.01,
+        }
+    }
+
+    fn binary_market() -> GammaMarket {
+        GammaMarket {
+            question: "Will it happen?".into(),
+            condition_id: "0xabc".into(),
+            outcome_prices: Some(r#"["0.45","0.55"]"#.into()),
+            outcomes: Some(r#"["Yes","No"]"#.into()),
+            clob_token_ids: Some(r#"["111","222"]"#.into()),
+            liquidity: Some("5000".into()),
+            accepting_orders: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pair_below_max_price_is_bought_in_matched_size() {
+        let config = Config::default();
+        let yes = book(&[(0.45, 100.0), (0.47, 50.0)]);
+        let no = book(&[(0.48, 120.0), (0.60, 500.0)]);
+        assert!(0.45 + 0.48 < max_pair_price(&config));
+
+        let opp = pair_opportunity(&binary_market(), &yes, &no, &config).expect("pair opportunity");
+        assert!(opp.is_basket());
+        assert_eq!(opp.legs.len(), 2);
+        assert_eq!(opp.legs[0].token_id, "111");
+        assert_eq!(opp.legs[1].token_id, "222");
+        // 100 @ 0.93, then 20 @ 0.95 until the NO side's first level runs out
+        assert_eq!(opp.max_shares, Some(120.0));
+        assert!((opp.legs[0].price - 0.47).abs() < 1e-9);
+        assert!((opp.legs[1].price - 0.48).abs() < 1e-9);
+        assert_eq!(opp.guaranteed_payout, Some(1.0));
+    }
+
+    #[test]
+    fn pair_at_or_above_payout_is_skipped() {
+        let config = Config::default();
+        let yes = book(&[(0.50, 100.0)]);
+        let no = book(&[(0.51, 100.0)]);
+        assert!(pair_opportunity(&binary_market(), &yes, &no, &config).is_none());
+    }
+}
@@ -1,7 +1,13 @@
-use tracing::{info, debug};
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use tracing::{info, debug, warn};
 use crate::config::Config;
+use crate::orderbook::{OrderBook, OrderBookClient};
 use crate::scanner::MarketOpportunity;
 
+/// Maximum in-flight order book requests while filtering
+const BOOK_FETCH_CONCURRENCY: usize = 4;
+
 /// Filters and ranks opportunities based on strategy rules
 pub struct Strategy;
 
@@ -10,15 +16,14 @@ impl Strategy {
     pub fn filter_opportunities(
         opportunities: Vec<MarketOpportunity>,
         config: &Config,
-        existing_positions: &[String], // condition_ids of existing positions
+        existing_positions: &HashSet<String>, // condition_ids of held positions and open orders
     ) -> Vec<MarketOpportunity> {
-        let mut filtered: Vec<MarketOpportunity> = opportunities
+        let mut filtered: Vec<MarketOpportunity> = merge_opportunities(opportunities, config)
             .into_iter()
             .filter(|opp| {
-                // 1. Price check - buy side must be within max price
-                let buy_price = opp.yes_price.min(opp.no_price);
-                if buy_price > config.max_price_decimal() {
-                    debug!("Skipping {} - price {:.4} above max {:.2}", opp.question, buy_price, config.max_price_decimal());
+                // 1. Price check - buy side must be within max price (baskets are priced on edge instead)
+                if !opp.is_basket() && opp.price > config.max_price_decimal() {
+                    debug!("Skipping {} - price {:.4} above max {:.2}", opp.question, opp.price, config.max_price_decimal());
                     return false;
                 }
 
@@ -29,27 +34,28 @@ impl Strategy {
                 }
 
                 // 3. Duplicate check - don't buy same market twice
-                if existing_positions.contains(&opp.condition_id) {
+                if existing_positions.contains(&opp.condition_id)
+                    || opp.legs.iter().any(|l| existing_positions.contains(&l.condition_id))
+                {
                     debug!("Skipping {} - already have position", opp.question);
                     return false;
                 }
 
-                // 4. Must have valid token ID for trading
-                if opp.token_id.is_empty() {
+                // 4. Must have valid token ID(s) for trading
+                let has_tokens = if opp.is_basket() {
+                    opp.legs.iter().all(|l| !l.token_id.is_empty())
+                } else {
+                    !opp.token_id.is_empty()
+                };
+                if !has_tokens {
                     debug!("Skipping {} - no token ID", opp.question);
                     return false;
                 }
 
                 // 5. Category filter (if configured)
-                if !config.categories.is_empty() {
-                    let question_lower = opp.question.to_lowercase();
-                    let matches_category = config.categories.iter().any(|cat| {
-                        let keywords = category_keywords(cat);
-                        keywords.iter().any(|kw| question_lower.contains(kw))
-                    });
-                    if !matches_category {
-                        return false;
-                    }
+                if !config.categories.is_empty() && !matches_categories(opp, config) {
+                    debug!("Skipping {} - category {:?} not in {:?}", opp.question, opp.category, config.categories);
+                    return false;
                 }
 
                 // 6. Volume filter
@@ -57,6 +63,35 @@ impl Strategy {
                     return false;
                 }
 
+                // 7. Model expected value (only when the model has a prediction)
+                if let (Some(min_ev), Some(ev)) = (config.min_expected_value, opp.expected_value) {
+                    if ev < min_ev {
+                        debug!("Skipping {} - expected value {:+.1}% below min {:+.1}%", opp.question, ev * 100.0, min_ev * 100.0);
+                        return false;
+                    }
+                }
+
+                // 8. Time to resolution
+                match opp.days_to_resolution {
+                    None if !config.allow_missing_end_date => {
+                        debug!("Skipping {} - no end date", opp.question);
+                        return false;
+                    }
+                    Some(days) if days <= 0.0 => {
+                        debug!("Skipping {} - end date passed, awaiting resolution", opp.question);
+                        return false;
+                    }
+                    Some(days) if config.min_days_to_resolution.is_some_and(|min| days < min) => {
+                        debug!("Skipping {} - resolves in {:.1} days", opp.question, days);
+                        return false;
+                    }
+                    Some(days) if config.max_days_to_resolution.is_some_and(|max| days > max) => {
+                        debug!("Skipping {} - resolves in {:.0} days", opp.question, days);
+                        return false;
+                    }
+                    _ => {}
+                }
+
                 true
             })
             .collect();
@@ -67,6 +102,158 @@ impl Strategy {
         info!("📋 Strategy: {} opportunities passed filters", filtered.len());
         filtered
     }
+
+    /// Check the top `book_filter_shortlist` opportunities (already ranked) against live
+    /// order books: spread at entry, and the share of nearby ask depth our order would take.
+    /// Opportunities outside the shortlist wait for a later cycle.
+    pub async fn filter_by_order_book(
+        mut opportunities: Vec<MarketOpportunity>,
+        config: &Config,
+        books: &OrderBookClient,
+    ) -> Vec<MarketOpportunity> {
+        if config.book_filter_shortlist == 0 {
+            return opportunities;
+        }
+        opportunities.truncate(config.book_filter_shortlist);
+
+        let mut token_ids: Vec<&str> = opportunities.iter()
+            .flat_map(|opp| entry_legs(opp).into_iter().map(|(token_id, _)| token_id))
+            .collect();
+        token_ids.sort_unstable();
+        token_ids.dedup();
+
+        let fetched: Vec<(&str, anyhow::Result<OrderBook>)> = stream::iter(token_ids)
+            .map(|token_id| async move { (token_id, books.fetch(token_id).await) })
+            .buffer_unordered(BOOK_FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut order_books: HashMap<String, OrderBook> = HashMap::new();
+        for (token_id, result) in fetched {
+            match result {
+                Ok(book) => { order_books.insert(token_id.to_string(), book); }
+                Err(e) => warn!("Failed to fetch order book for {}: {}", token_id, e),
+            }
+        }
+
+        let shortlisted = opportunities.len();
+        let filtered: Vec<MarketOpportunity> = opportunities.into_iter()
+            .filter(|opp| passes_book_checks(opp, config, &order_books))
+            .collect();
+
+        info!("📖 Order books: {}/{} shortlisted opportunities passed spread/depth checks", filtered.len(), shortlisted);
+        filtered
+    }
+}
+
+/// Merge opportunities for the same token (or basket) found by several sources into one,
+/// keeping the highest-scoring variant and combining discovery reasons and score
+fn merge_opportunities(opportunities: Vec<MarketOpportunity>, config: &Config) -> Vec<MarketOpportunity> {
+    let mut merged: Vec<MarketOpportunity> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for opp in opportunities {
+        // Opposite outcomes of one market are different trades, and a basket differs from its legs
+        let key = if opp.is_basket() { format!("basket:{}", opp.condition_id) } else { opp.token_id.clone() };
+        let Some(&i) = index.get(&key) else {
+            index.insert(key, merged.len());
+            merged.push(opp);
+            continue;
+        };
+
+        let existing = &mut merged[i];
+        let mut reasons = std::mem::take(&mut existing.reasons);
+        for reason in &opp.reasons {
+            if !reasons.contains(reason) {
+                reasons.push(reason.clone());
+            }
+        }
+        if opp.score > existing.score {
+            *existing = opp;
+        }
+        existing.reasons = reasons;
+    }
+
+    for opp in &mut merged {
+        if opp.reasons.len() > 1 {
+            opp.add_score("sources", config.scoring.extra_source_bonus * (opp.reasons.len() - 1) as f64);
+            debug!("Merged {} found by {}", opp.question, opp.reasons_label());
+        }
+    }
+
+    merged
+}
+
+/// (token_id, entry price) of every token an opportunity buys
+fn entry_legs(opp: &MarketOpportunity) -> Vec<(&str, f64)> {
+    if opp.is_basket() {
+        opp.legs.iter().map(|l| (l.token_id.as_str(), l.price)).collect()
+    } else {
+        vec![(opp.token_id.as_str(), opp.price)]
+    }
+}
+
+fn passes_book_checks(opp: &MarketOpportunity, config: &Config, order_books: &HashMap<String, OrderBook>) -> bool {
+    // Every leg is bought in the same number of shares
+    let shares = (config.max_per_trade_usd / opp.price).min(opp.max_shares.unwrap_or(f64::INFINITY));
+
+    entry_legs(opp).into_iter().all(|(token_id, price)| {
+        let Some(book) = order_books.get(token_id) else {
+            debug!("Skipping {} - no order book", opp.question);
+            return false;
+        };
+
+        let Some(spread) = book.spread() else {
+            debug!("Skipping {} - no asks on the book", opp.question);
+            return false;
+        };
+        if spread * 100.0 > config.max_spread_cents {
+            debug!("Skipping {} - spread {:.1}¢ above max {:.1}¢", opp.question, spread * 100.0, config.max_spread_cents);
+            return false;
+        }
+
+        let depth = book.ask_depth_within(price, config.depth_ticks);
+        if shares > depth * config.max_depth_fraction {
+            debug!("Skipping {} - {:.0} shares vs {:.0} ask depth within {} ticks of ${:.4}",
+                opp.question, shares, depth, config.depth_ticks, price);
+            return false;
+        }
+
+        true
+    })
+}
+
+/// Match an opportunity against `config.categories` using Gamma tags, falling back to
+/// question keywords (on word boundaries) only for markets without tag metadata
+fn matches_categories(opp: &MarketOpportunity, config: &Config) -> bool {
+    let has_tags = !opp.tags.is_empty() || !opp.category.is_empty();
+    if has_tags {
+        let category = opp.category.to_lowercase();
+        return config.categories.iter().any(|cat| {
+            let wanted = cat.trim().to_lowercase();
+            let wanted_slug = wanted.replace(' ', "-");
+            category == wanted || opp.tags.iter().any(|t| *t == wanted || *t == wanted_slug)
+        });
+    }
+
+    if !config.category_keyword_fallback {
+        return false;
+    }
+
+    let question_lower = opp.question.to_lowercase();
+    config.categories.iter().any(|cat| {
+        category_keywords(cat).iter().any(|kw| contains_word(&question_lower, kw))
+    })
+}
+
+/// Whether `keyword` appears in `text` delimited by non-alphanumeric characters
+fn contains_word(text: &str, keyword: &str) -> bool {
+    text.match_indices(keyword).any(|(start, _)| {
+        let end = start + keyword.len();
+        let before_ok = text[..start].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = text[end..].chars().next().is_none_or(|c| !c.is_alphanumeric());
+        before_ok && after_ok
+    })
 }
 
 /// Get keywords for a category name
@@ -1,9 +1,10 @@
 use anyhow::Result;
 use tracing::{info, warn};
 
+use crate::chain::Redeemed;
 use crate::config::Config;
 use crate::executor::Trade;
-use crate::positions::ExitSignal;
+use crate::positions::{ExitSignal, Settlement};
 use crate::scanner::MarketOpportunity;
 
 pub struct TelegramNotifier {
@@ -46,14 +47,14 @@ impl TelegramNotifier {
             💰 Max/trade: ${:.2}\n\
             📊 Max/day: ${:.2}\n\
             🎯 Buy price: ≤ {}¢\n\
-            📈 Auto-sell: {}x entry\n\
+            📈 Take-profit: {}\n\
             🔄 Scan interval: {}s\n\
             📁 Categories: {}",
             mode,
             config.max_per_trade_usd,
             config.max_daily_spend_usd,
             config.max_price_cents,
-            config.auto_sell_multiplier,
+            config.take_profit_label(),
             config.scan_interval_secs,
             if config.categories.is_empty() { "All".to_string() } else { config.categories.join(", ") },
         );
@@ -76,11 +77,13 @@ impl TelegramNotifier {
             📊 {} @ ${:.4}\n\
             📦 {:.0} shares = ${:.2}\n\
             🏷️ Score: {:.0}/100\n\
+            📁 Category: {}\n\
+            ⏰ Resolves: {}\n\
             💧 Liquidity: ${:.0}\n\
             📈 Vol 24h: ${:.0}\n\
             🔗 [View Market]({})",
             emoji,
-            opp.discovery_type,
+            opp.reasons_label(),
             trade.side,
             trade.question,
             trade.side,
@@ -88,6 +91,8 @@ impl TelegramNotifier {
             trade.size,
             trade.cost_usd,
             opp.score,
+            if opp.category.is_empty() { "-" } else { &opp.category },
+            opp.days_to_resolution.map(|d| format!("in {:.0} days", d)).unwrap_or_else(|| "unknown".to_string()),
             opp.liquidity,
             opp.volume_24h,
             trade.url,
@@ -96,14 +101,71 @@ impl TelegramNotifier {
         self.send_message(&msg).await
     }
 
-    /// Send exit signal notification
-    pub async fn send_exit_signal(&self, signal: &ExitSignal) -> Result<()> {
+    /// Send notification for a multi-leg basket trade
+    pub async fn send_basket(&self, trades: &[Trade], opp: &MarketOpportunity) -> Result<()> {
+        let shares = trades.first().map(|t| t.size).unwrap_or(0.0);
+        let cost: f64 = trades.iter().map(|t| t.cost_usd).sum();
+        let payout = opp.guaranteed_payout.unwrap_or(0.0) * shares;
+        let legs: Vec<String> = trades.iter()
+            .map(|t| format!("• {} {} @ ${:.4}", t.side, truncate(&t.question, 50), t.price))
+            .collect();
+
+        let msg = format!(
+            "🧺 *{} Basket Placed*\n\n\
+            {}\n\
+            📦 {:.0} shares x {} legs = ${:.2}\n\
+            💵 Guaranteed payout: ${:.2} (+${:.2})\n\
+            ⏰ Resolves: {}\n\n\
+            {}\n\n\
+            🔗 [View Event]({})",
+            opp.reasons_label(),
+            opp.question,
+            shares,
+            trades.len(),
+            cost,
+            payout,
+            payout - cost,
+            opp.days_to_resolution.map(|d| format!("in {:.0} days", d)).unwrap_or_else(|| "unknown".to_string()),
+            legs.join("\n"),
+            opp.url,
+        );
+
+        self.send_message(&msg).await
+    }
+
+    /// Send notification for shares matched on a live order
+    pub async fn send_fill(&self, trade: &Trade, shares: f64, price: f64) -> Result<()> {
+        let title = if trade.status == crate::executor::TradeStatus::Filled { "Order Filled" } else { "Partial Fill" };
+
+        let msg = format!(
+            "✅ *{}*\n\n\
+            {} {}\n\
+            📊 +{:.0} shares @ ${:.4} = ${:.2}\n\
+            📦 Filled: {:.0}/{:.0} shares\n\
+            🔗 [View Market]({})",
+            title,
+            trade.side,
+            trade.question,
+            shares,
+            price,
+            shares * price,
+            trade.filled_size,
+            trade.size,
+            trade.url,
+        );
+
+        self.send_message(&msg).await
+    }
+
+    /// Send exit notification; `placed` says whether a sell order went out
+    pub async fn send_exit_signal(&self, signal: &ExitSignal, placed: bool) -> Result<()> {
         let msg = format!(
-            "🎯 *Exit Signal: {}*\n\n\
+            "🎯 *{}: {}*\n\n\
             {} {}\n\
             📊 Entry: ${:.4} → Now: ${:.4}\n\
             📈 P/L: {:+.1}%\n\
             🔄 Action: {} ({:.0} shares)",
+            if placed { "Selling" } else { "Exit Signal" },
             signal.signal_type,
             signal.side,
             signal.question,
@@ -117,6 +179,64 @@ impl TelegramNotifier {
         self.send_message(&msg).await
     }
 
+    /// Send notification for a position settled by its market resolving
+    pub async fn send_settlement(&self, settlement: &Settlement) -> Result<()> {
+        let pos = &settlement.position;
+        let mut msg = format!(
+            "🏁 *Market Resolved: {}*\n\n\
+            {} {}\n\
+            📊 {:.0} shares @ ${:.4} → ${:.2}\n\
+            💵 Payout: ${:.2} | P/L: ${:+.2}\n\
+            🔗 [View Market]({})",
+            settlement.outcome_label(),
+            pos.side,
+            pos.question,
+            pos.shares,
+            pos.entry_price,
+            settlement.payout_price,
+            settlement.proceeds,
+            settlement.pnl,
+            pos.url,
+        );
+
+        if let Some(basket) = &settlement.basket {
+            msg.push_str(&format!(
+                "\n\n🧺 *Basket settled*: {} legs, cost ${:.2}, payout ${:.2} (P/L ${:+.2})",
+                basket.legs, basket.cost_usd, basket.proceeds, basket.proceeds - basket.cost_usd,
+            ));
+        }
+
+        self.send_message(&msg).await
+    }
+
+    /// Send notification for winnings redeemed on chain
+    pub async fn send_redemption(&self, redeemed: &Redeemed) -> Result<()> {
+        let msg = format!(
+            "💸 *Winnings Redeemed*\n\n\
+            {}\n\
+            🔗 [View Transaction](https://polygonscan.com/tx/{})",
+            redeemed.question,
+            redeemed.tx_hash,
+        );
+
+        self.send_message(&msg).await
+    }
+
+    /// Send discrepancies found reconciling holdings against the wallet
+    pub async fn send_reconciliation(&self, discrepancies: &[String]) -> Result<()> {
+        let lines: Vec<String> = discrepancies.iter().take(10)
+            .map(|d| format!("• {}", truncate(d, 120)))
+            .collect();
+        let more = discrepancies.len().saturating_sub(lines.len());
+
+        let mut msg = format!("🔎 *Reconciliation: {} discrepancies*\n\n{}", discrepancies.len(), lines.join("\n"));
+        if more > 0 {
+            msg.push_str(&format!("\n…and {} more", more));
+        }
+
+        self.send_message(&msg).await
+    }
+
     /// Send daily portfolio summary
     pub async fn send_daily_summary(&self, summary: &str, risk_summary: &str) -> Result<()> {
         let msg = format!(
@@ -137,15 +257,19 @@ impl TelegramNotifier {
         let mut msg = format!("🔍 *Found {} Opportunities*\n\n", opps.len());
 
         for (i, opp) in top.enumerate() {
-            let buy_price = opp.yes_price.min(opp.no_price);
-            let side = if opp.yes_price <= opp.no_price { "YES" } else { "NO" };
             msg.push_str(&format!(
-                "{}. {} {} @ ${:.4} (Score: {:.0})\n{}\n\n",
+                "{}. {} {} @ ${:.4} (Score: {:.0})\n{}\n🧮 {}{}\n{}\n\n",
                 i + 1,
-                side,
+                opp.outcome,
                 truncate(&opp.question, 50),
-                buy_price,
+                opp.price,
                 opp.score,
+                opp.reasons_label(),
+                opp.score_breakdown.summary(),
+                match (opp.predicted_probability, opp.expected_value) {
+                    (Some(p), Some(ev)) => format!("\n🤖 Model: {:.1}% | EV {:+.0}%", p * 100.0, ev * 100.0),
+                    _ => String::new(),
+                },
                 opp.url,
             ));
         }
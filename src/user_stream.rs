@@ -0,0 +1,260 @@
+use anyhow::{Result, Context};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn, debug};
+
+use crate::clob::ApiCredentials;
+
+const USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+const PING_INTERVAL_SECS: u64 = 10;
+const MAX_RECONNECT_DELAY_SECS: u64 = 60;
+
+/// Order lifecycle stage reported on the user channel
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEventKind {
+    Placement,
+    Update,
+    Cancellation,
+}
+
+/// A single order's share of a matched trade
+#[derive(Debug, Clone)]
+pub struct OrderFill {
+    pub order_id: String,
+    pub asset_id: String,
+    pub matched_size: f64,
+    pub price: f64,
+    /// `None` when the event omits the fee rate
+    pub fee_rate_bps: Option<f64>,
+}
+
+/// Events pushed by the authenticated user channel
+#[derive(Debug, Clone)]
+pub enum UserEvent {
+    Order {
+        order_id: String,
+        asset_id: String,
+        kind: OrderEventKind,
+        original_size: f64,
+        size_matched: f64,
+        price: f64,
+    },
+    Trade {
+        trade_id: String,
+        status: String,
+        fills: Vec<OrderFill>,
+    },
+}
+
+/// Raw `order` message from the user channel
+#[derive(Debug, Deserialize)]
+struct RawOrderEvent {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    asset_id: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    original_size: String,
+    #[serde(default)]
+    size_matched: String,
+    #[serde(default)]
+    price: String,
+}
+
+/// Raw `trade` message from the user channel
+#[derive(Debug, Deserialize)]
+struct RawTradeEvent {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    taker_order_id: String,
+    #[serde(default)]
+    asset_id: String,
+    #[serde(default)]
+    size: String,
+    #[serde(default)]
+    price: String,
+    #[serde(default)]
+    fee_rate_bps: String,
+    #[serde(default)]
+    maker_orders: Vec<RawMakerOrder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawMakerOrder {
+    #[serde(default)]
+    order_id: String,
+    #[serde(default)]
+    asset_id: String,
+    #[serde(default)]
+    matched_amount: String,
+    #[serde(default)]
+    price: String,
+    #[serde(default)]
+    fee_rate_bps: String,
+}
+
+/// Background consumer of the CLOB user WebSocket channel
+pub struct UserStream;
+
+impl UserStream {
+    /// Spawn the background task and return the receiving end of its event channel
+    pub fn spawn(creds: ApiCredentials) -> mpsc::UnboundedReceiver<UserEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut delay = 1u64;
+            loop {
+                match Self::run(&creds, &tx).await {
+                    Ok(_) => {
+                        info!("🔌 User channel closed - reconnecting");
+                        delay = 1;
+                    }
+                    Err(e) => warn!("⚠️ User channel error: {} (reconnecting in {}s)", e, delay),
+                }
+
+                if tx.is_closed() {
+                    break;
+                }
+
+                tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY_SECS);
+            }
+        });
+
+        rx
+    }
+
+    /// Run a single connection until it closes or errors
+    async fn run(creds: &ApiCredentials, tx: &mpsc::UnboundedSender<UserEvent>) -> Result<()> {
+        let (ws, _) = connect_async(USER_WS_URL).await.context("Failed to connect to user channel")?;
+        let (mut write, mut read) = ws.split();
+
+        let subscribe = serde_json::json!({
+            "auth": {
+                "apiKey": creds.api_key,
+                "secret": creds.secret,
+                "passphrase": creds.passphrase,
+            },
+            "markets": [],
+            "type": "user",
+        });
+        write.send(Message::Text(subscribe.to_string())).await?;
+        info!("🔌 Subscribed to user channel");
+
+        let mut ping = tokio::time::interval(std::time::Duration::from_secs(PING_INTERVAL_SECS));
+
+        loop {
+            tokio::select! {
+                _ = ping.tick() => {
+                    write.send(Message::Text("PING".into())).await?;
+                }
+                msg = read.next() => {
+                    let msg = match msg {
+                        Some(m) => m?,
+                        None => return Ok(()),
+                    };
+                    match msg {
+                        Message::Text(text) => {
+                            for event in parse_events(&text) {
+                                if tx.send(event).is_err() {
+                                    return Ok(());
+                                }
+                            }
+                        }
+                        Message::Ping(data) => write.send(Message::Pong(data)).await?,
+                        Message::Close(_) => return Ok(()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse a user channel frame (single object or array of objects) into events
+fn parse_events(text: &str) -> Vec<UserEvent> {
+    if text == "PONG" {
+        return Vec::new();
+    }
+
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(_) => {
+            debug!("Ignoring non-JSON user channel frame: {}", text);
+            return Vec::new();
+        }
+    };
+
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+
+    items.into_iter().filter_map(parse_event).collect()
+}
+
+fn parse_event(value: serde_json::Value) -> Option<UserEvent> {
+    let event_type = value.get("event_type")?.as_str()?.to_string();
+
+    match event_type.as_str() {
+        "order" => {
+            let raw: RawOrderEvent = serde_json::from_value(value).ok()?;
+            let kind = match raw.kind.as_str() {
+                "PLACEMENT" => OrderEventKind::Placement,
+                "UPDATE" => OrderEventKind::Update,
+                "CANCELLATION" => OrderEventKind::Cancellation,
+                other => {
+                    debug!("Unknown order event type: {}", other);
+                    return None;
+                }
+            };
+            Some(UserEvent::Order {
+                order_id: raw.id,
+                asset_id: raw.asset_id,
+                kind,
+                original_size: parse_num(&raw.original_size),
+                size_matched: parse_num(&raw.size_matched),
+                price: parse_num(&raw.price),
+            })
+        }
+        "trade" => {
+            let raw: RawTradeEvent = serde_json::from_value(value).ok()?;
+            let mut fills = Vec::new();
+            if !raw.taker_order_id.is_empty() {
+                fills.push(OrderFill {
+                    order_id: raw.taker_order_id,
+                    asset_id: raw.asset_id,
+                    matched_size: parse_num(&raw.size),
+                    price: parse_num(&raw.price),
+                    fee_rate_bps: raw.fee_rate_bps.parse().ok(),
+                });
+            }
+            for maker in raw.maker_orders {
+                fills.push(OrderFill {
+                    order_id: maker.order_id,
+                    asset_id: maker.asset_id,
+                    matched_size: parse_num(&maker.matched_amount),
+                    price: parse_num(&maker.price),
+                    fee_rate_bps: maker.fee_rate_bps.parse().ok(),
+                });
+            }
+            Some(UserEvent::Trade {
+                trade_id: raw.id,
+                status: raw.status,
+                fills,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_num(s: &str) -> f64 {
+    s.parse().unwrap_or(0.0)
+}
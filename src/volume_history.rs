@@ -0,0 +1,177 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// Minimum seconds between writes of the history file
+const SAVE_INTERVAL_SECS: u64 = 300;
+
+/// A single observation of a market's volume and price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sample {
+    pub timestamp: DateTime<Utc>,
+    pub volume_24h: f64,
+    pub volume_total: f64,
+    pub price: f64,
+}
+
+/// Rolling samples plus EWMA statistics of a market's traded volume per minute.
+/// The EWMA forgets at `halflife_secs`; a gap longer than the window restarts it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketSeries {
+    pub samples: VecDeque<Sample>,
+    ewma_rate: f64,
+    ewma_var: f64,
+    observations: usize,
+}
+
+/// Result of scoring one new observation against a market's baseline
+#[derive(Debug, Clone)]
+pub struct SpikeReading {
+    /// Volume traded per minute since the previous sample
+    pub rate: f64,
+    /// Baseline (EWMA) volume per minute before this sample
+    pub baseline: f64,
+    pub zscore: f64,
+    /// Rate observations that went into the baseline
+    pub observations: usize,
+}
+
+/// Tuning for rolling spike detection
+#[derive(Debug, Clone)]
+pub struct SpikeParams {
+    pub window_secs: i64,
+    pub halflife_secs: f64,
+}
+
+impl MarketSeries {
+    /// Append a sample, returning the volume rate's z-score against the prior baseline
+    fn observe(&mut self, sample: Sample, params: &SpikeParams) -> Option<SpikeReading> {
+        // A reused snapshot carries no new information
+        if self.samples.back().is_some_and(|prev| prev.timestamp >= sample.timestamp) {
+            return None;
+        }
+
+        // After a gap longer than the window the baseline no longer describes the market
+        let cutoff = sample.timestamp - chrono::Duration::seconds(params.window_secs);
+        if self.samples.back().is_some_and(|prev| prev.timestamp < cutoff) {
+            *self = Self::default();
+        }
+
+        let reading = self.samples.back().map(|prev| {
+            let dt_secs = sample.timestamp.signed_duration_since(prev.timestamp).num_milliseconds() as f64 / 1000.0;
+
+            // Lifetime volume is monotonic; fall back to the rolling 24h figure if it is missing
+            let traded = if sample.volume_total > 0.0 && prev.volume_total > 0.0 {
+                sample.volume_total - prev.volume_total
+            } else {
+                sample.volume_24h - prev.volume_24h
+            };
+            let rate = traded.max(0.0) / (dt_secs / 60.0);
+
+            let baseline = self.ewma_rate;
+            // Floor the deviation so a flat history doesn't turn every trade into a spike
+            let std_dev = self.ewma_var.sqrt().max(baseline * 0.25).max(1.0);
+            let zscore = (rate - baseline) / std_dev;
+            let observations = self.observations;
+
+            // Update EWMA mean/variance with a time-based decay
+            let alpha = 1.0 - (-std::f64::consts::LN_2 * dt_secs / params.halflife_secs).exp();
+            if self.observations == 0 {
+                self.ewma_rate = rate;
+                self.ewma_var = 0.0;
+            } else {
+                let diff = rate - self.ewma_rate;
+                self.ewma_rate += alpha * diff;
+                self.ewma_var = (1.0 - alpha) * (self.ewma_var + alpha * diff * diff);
+            }
+            self.observations += 1;
+
+            SpikeReading { rate, baseline, zscore, observations }
+        });
+
+        self.samples.push_back(sample);
+        let cutoff = Utc::now() - chrono::Duration::seconds(params.window_secs);
+        while self.samples.len() > 1 && self.samples.front().is_some_and(|s| s.timestamp < cutoff) {
+            self.samples.pop_front();
+        }
+
+        reading
+    }
+}
+
+/// Per-market time series of volume and price samples, optionally persisted to disk
+pub struct VolumeHistory {
+    series: HashMap<String, MarketSeries>,
+    path: Option<PathBuf>,
+    /// Samples were added since the last write
+    dirty: bool,
+    last_saved: Option<Instant>,
+}
+
+impl VolumeHistory {
+    /// Create a history, loading previous samples from `path` if it exists
+    pub fn new(path: Option<&str>) -> Self {
+        let path = path.filter(|p| !p.is_empty()).map(PathBuf::from);
+        let series = path.as_ref()
+            .filter(|p| p.exists())
+            .and_then(|p| match Self::load(p) {
+                Ok(series) => {
+                    info!("📂 Loaded volume history for {} markets", series.len());
+                    Some(series)
+                }
+                Err(e) => {
+                    warn!("Failed to load volume history: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self { series, path, dirty: false, last_saved: None }
+    }
+
+    /// Follow a hot-reloaded `spike_history_path`; the next save writes to the new file
+    pub fn set_path(&mut self, path: Option<&str>) {
+        let path = path.filter(|p| !p.is_empty()).map(PathBuf::from);
+        if path != self.path {
+            info!("📂 Volume history path changed to {:?}", path);
+            self.path = path;
+            self.dirty = true;
+            self.last_saved = None;
+        }
+    }
+
+    fn load(path: &PathBuf) -> Result<HashMap<String, MarketSeries>> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).context("Invalid volume history file")
+    }
+
+    /// Record a sample for a market and score it against the market's baseline
+    pub fn observe(&mut self, market_id: &str, sample: Sample, params: &SpikeParams) -> Option<SpikeReading> {
+        self.dirty = true;
+        self.series.entry(market_id.to_string()).or_default().observe(sample, params)
+    }
+
+    /// Drop markets with no sample inside the window
+    pub fn prune(&mut self, window_secs: i64) {
+        let cutoff = Utc::now() - chrono::Duration::seconds(window_secs);
+        self.series.retain(|_, s| s.samples.back().is_some_and(|last| last.timestamp >= cutoff));
+    }
+
+    /// Write the history to disk if persistence is configured, it changed, and the
+    /// last write is at least `SAVE_INTERVAL_SECS` old
+    pub fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        if !self.dirty || self.last_saved.is_some_and(|t| t.elapsed().as_secs() < SAVE_INTERVAL_SECS) {
+            return Ok(());
+        }
+        let content = serde_json::to_string(&self.series)?;
+        std::fs::write(path, content).context("Failed to write volume history")?;
+        self.dirty = false;
+        self.last_saved = Some(Instant::now());
+        Ok(())
+    }
+}